@@ -1,12 +1,15 @@
 use std::io::Write as _;
 use std::str::FromStr;
 
+use base64::Engine as _;
 use clap::{Parser, Subcommand};
 use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
 use console::{Key, Style, Term};
+use futures::future::join_all;
 use hf_providers_core::{
     api::{parse_model, HfClient},
     cloud,
+    config::{AppConfig, Selection},
     estimate::{self, Fit},
     hardware::{self, Runtime},
     model::Model,
@@ -14,29 +17,97 @@ use hf_providers_core::{
     reference::REFERENCE_MODELS,
     snippet::{self, Lang},
 };
+use serde::Serialize;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
 
 // ── Palette ──────────────────────────────────────────────────────────
 
-fn s_header() -> Style { Style::new().color256(252).bold() }  // bright gray, bold
-fn s_dim() -> Style    { Style::new().color256(248) }         // light gray
-fn s_tree() -> Style   { Style::new().color256(245) }         // mid gray
-fn s_hint() -> Style   { Style::new().color256(243) }         // soft gray
-fn s_hot() -> Style    { Style::new().color256(114) }         // green
-fn s_warm() -> Style   { Style::new().color256(214) }         // amber
-fn s_cold() -> Style   { Style::new().color256(248) }         // light gray
-fn s_err() -> Style    { Style::new().color256(167) }         // red
-fn s_price() -> Style  { Style::new().color256(109) }         // teal
-fn s_bold() -> Style   { Style::new().bold() }
-fn s_accent() -> Style { Style::new().color256(109) }         // teal accent
-fn s_label() -> Style  { Style::new().color256(146) }         // muted lavender
-fn s_heart() -> Style  { Style::new().color256(168) }         // rose
-fn s_param() -> Style  { Style::new().color256(139) }         // mauve
+/// Process-wide rendering mode, set once at startup from `--plain`,
+/// `NO_COLOR`, or a non-TTY stdout. Every `s_*()`/`sep`/`readiness_str`
+/// call consults this so one flag downgrades the entire UI consistently.
+static PLAIN_MODE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Process-wide "basic mode" switch, set once at startup from `--basic`.
+/// Basic mode implies plain mode (no color) and additionally collapses
+/// every `comfy_table` into one `key: value value ...` line per row, for
+/// dumb terminals, logs, and grepping.
+static BASIC_MODE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Decide and latch the rendering mode for this run. Call once, early in
+/// `main()`, before any `s_*()`/`sep`/`readiness_str` call.
+fn init_plain_mode(cli_plain: bool, cli_basic: bool) {
+    let plain = cli_plain
+        || cli_basic
+        || std::env::var_os("NO_COLOR").is_some()
+        || !Term::stdout().is_term();
+    PLAIN_MODE.store(plain, std::sync::atomic::Ordering::Relaxed);
+    BASIC_MODE.store(cli_basic, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn is_plain() -> bool {
+    PLAIN_MODE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+fn is_basic() -> bool {
+    BASIC_MODE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// In plain mode every palette function downgrades to an unstyled `Style`,
+/// so `.apply_to()` at every call site emits bare text with no ANSI codes.
+fn plain_or(s: Style) -> Style {
+    if is_plain() { Style::new() } else { s }
+}
+
+/// `comfy_table` cell color, downgraded to the terminal default in plain mode.
+fn tcolor(n: u8) -> Color {
+    if is_plain() { Color::Reset } else { Color::AnsiValue(n) }
+}
+
+/// Box-drawing dash (U+2500) placeholder for a missing value, swapped for an ASCII hyphen in plain mode.
+fn dash() -> &'static str {
+    if is_plain() { "-" } else { "\u{2500}" }
+}
+
+/// Likes glyph, swapped for ASCII in plain mode.
+fn heart() -> &'static str {
+    if is_plain() { "+" } else { "\u{2665}" }
+}
+
+fn s_header() -> Style { plain_or(Style::new().color256(252).bold()) }  // bright gray, bold
+fn s_dim() -> Style    { plain_or(Style::new().color256(248)) }         // light gray
+fn s_tree() -> Style   { plain_or(Style::new().color256(245)) }         // mid gray
+fn s_hint() -> Style   { plain_or(Style::new().color256(243)) }         // soft gray
+fn s_hot() -> Style    { plain_or(Style::new().color256(114)) }         // green
+fn s_warm() -> Style   { plain_or(Style::new().color256(214)) }         // amber
+fn s_cold() -> Style   { plain_or(Style::new().color256(248)) }         // light gray
+fn s_err() -> Style    { plain_or(Style::new().color256(167)) }         // red
+fn s_price() -> Style  { plain_or(Style::new().color256(109)) }         // teal
+fn s_bold() -> Style   { plain_or(Style::new().bold()) }
+fn s_accent() -> Style { plain_or(Style::new().color256(109)) }         // teal accent
+fn s_label() -> Style  { plain_or(Style::new().color256(146)) }         // muted lavender
+fn s_heart() -> Style  { plain_or(Style::new().color256(168)) }         // rose
+fn s_param() -> Style  { plain_or(Style::new().color256(139)) }         // mauve
 
 fn sep(width: usize) -> String {
-    s_tree().apply_to("\u{2500}".repeat(width)).to_string()
+    if is_plain() {
+        "-".repeat(width)
+    } else {
+        s_tree().apply_to("\u{2500}".repeat(width)).to_string()
+    }
 }
 
 fn readiness_str(r: Readiness) -> String {
+    if is_plain() {
+        return match r {
+            Readiness::Hot         => "* hot".to_string(),
+            Readiness::Warm        => "o warm".to_string(),
+            Readiness::Cold        => "- cold".to_string(),
+            Readiness::Unavailable => "x unavail".to_string(),
+        };
+    }
     match r {
         Readiness::Hot         => format!("{}", s_hot().apply_to("\u{25cf} hot")),
         Readiness::Warm        => format!("{}", s_warm().apply_to("\u{25d0} warm")),
@@ -95,6 +166,124 @@ struct Cli {
 
     #[arg(long, short)]
     json: bool,
+
+    /// Machine-readable output: json, csv, tsv, or dot (model search only)
+    /// (default: human, or tsv when piped)
+    #[arg(long, global = true)]
+    format: Option<String>,
+
+    /// Shorthand for --format csv
+    #[arg(long, global = true)]
+    csv: bool,
+
+    /// Strip colors, box-drawing, and Unicode glyphs (also triggered by
+    /// NO_COLOR or a non-TTY stdout)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Condense tables into one `key: value value ...` line per row, with
+    /// no color or box-drawing (implies --plain)
+    #[arg(long, global = true)]
+    basic: bool,
+
+    /// Path to a config file (defaults to ~/.config/hf-providers/config.toml)
+    #[arg(long)]
+    config: Option<std::path::PathBuf>,
+}
+
+/// Output mode shared by every tabular command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Human,
+    Json,
+    Csv,
+    Tsv,
+    /// Graphviz DOT, only meaningful for `hf-providers <model>` (see
+    /// `model_dot`) — every other command treats it like `Human`.
+    Dot,
+}
+
+impl OutputFormat {
+    /// CLI flag wins; otherwise default to TSV when stdout isn't a TTY so
+    /// piping into `cut`/`awk` just works.
+    fn resolve(cli: &Cli) -> Self {
+        if cli.json {
+            return OutputFormat::Json;
+        }
+        if cli.csv {
+            return OutputFormat::Csv;
+        }
+        match cli.format.as_deref() {
+            Some("json") => OutputFormat::Json,
+            Some("csv") => OutputFormat::Csv,
+            Some("tsv") => OutputFormat::Tsv,
+            Some("dot") => OutputFormat::Dot,
+            Some(other) => {
+                eprintln!(
+                    "{}",
+                    s_err().apply_to(format!("unknown --format '{other}', falling back to human"))
+                );
+                OutputFormat::Human
+            }
+            None => {
+                if Term::stdout().is_term() {
+                    OutputFormat::Human
+                } else {
+                    OutputFormat::Tsv
+                }
+            }
+        }
+    }
+
+    fn is_machine(self) -> bool {
+        !matches!(self, OutputFormat::Human | OutputFormat::Dot)
+    }
+}
+
+/// Emit `rows` (each row already rendered to plain strings, no ANSI, no
+/// abbreviated numbers) as CSV/TSV/JSON per `format`. Human mode is handled
+/// by each command's own `comfy_table` rendering and never reaches here.
+fn print_rows(format: OutputFormat, headers: &[&str], rows: &[Vec<String>]) {
+    match format {
+        OutputFormat::Human | OutputFormat::Dot => {}
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let sep = if format == OutputFormat::Csv { ',' } else { '\t' };
+            println!("{}", headers.join(&sep.to_string()));
+            for row in rows {
+                println!("{}", row.join(&sep.to_string()));
+            }
+        }
+        OutputFormat::Json => {
+            let objects = rows_to_json(headers, rows);
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&objects).unwrap_or_default()
+            );
+        }
+    }
+}
+
+/// Render `rows` as one `key: value value ...` line per row (the first
+/// column is the key) instead of a `comfy_table`, for `--basic` mode —
+/// no box-drawing, no color, grep-friendly.
+fn print_basic_rows(rows: &[Vec<String>]) {
+    for row in rows {
+        if let Some((key, rest)) = row.split_first() {
+            println!("  {key}: {}", rest.join(" "));
+        }
+    }
+}
+
+fn rows_to_json(headers: &[&str], rows: &[Vec<String>]) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    rows.iter()
+        .map(|row| {
+            headers
+                .iter()
+                .zip(row.iter())
+                .map(|(h, v)| ((*h).to_string(), serde_json::Value::String(v.clone())))
+                .collect()
+        })
+        .collect()
 }
 
 #[derive(Subcommand)]
@@ -102,8 +291,8 @@ enum Commands {
     /// Code snippet for a model.
     Snippet {
         model: String,
-        #[arg(long, short, default_value = "python")]
-        lang: String,
+        #[arg(long, short)]
+        lang: Option<String>,
         #[arg(long, short)]
         provider: Option<String>,
         #[arg(long)]
@@ -117,11 +306,15 @@ enum Commands {
         #[arg(long, short)]
         task: Option<String>,
     },
-    /// Live status across providers.
+    /// Live status across providers. Pass multiple models to compare side by side.
     Status {
-        model: String,
+        #[arg(required = true)]
+        models: Vec<String>,
         #[arg(long, short)]
         watch: Option<u64>,
+        /// Append one timestamped CSV row per provider per poll to this file
+        #[arg(long)]
+        log: Option<std::path::PathBuf>,
     },
     /// What can this GPU run?
     Machine {
@@ -130,19 +323,47 @@ enum Commands {
         /// Optional model to evaluate, e.g. deepseek-r1 or meta-llama/Llama-3.3-70B-Instruct
         model: Option<String>,
     },
-    /// Compare costs: API vs cloud GPU vs local GPU
+    /// Compare costs: API vs cloud GPU vs local GPU. Pass multiple models for a side-by-side comparison.
     Need {
-        /// Model to analyze, e.g. deepseek-r1 or meta-llama/Llama-3.3-70B-Instruct
-        model: String,
+        /// Model(s) to analyze, e.g. deepseek-r1 or meta-llama/Llama-3.3-70B-Instruct
+        #[arg(required = true)]
+        models: Vec<String>,
+        /// Expected monthly output volume in millions of tokens. Ranks API
+        /// vs cloud vs local at that volume and solves the break-even
+        /// crossover points (single-model only).
+        #[arg(long)]
+        volume: Option<f64>,
+    },
+    /// Update GPU and cloud pricing data from GitHub (skipped if the cache is still fresh)
+    Sync {
+        /// Re-download even if the cache is still within its TTL
+        #[arg(long)]
+        force: bool,
+        /// How fresh the cache must be, in hours, before a sync is skipped
+        #[arg(long, default_value_t = 24)]
+        ttl: u64,
     },
-    /// Update GPU and cloud pricing data from GitHub
-    Sync,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    init_plain_mode(cli.plain, cli.basic);
     let client = HfClient::with_auto_token();
+    let config = AppConfig::load(cli.config.as_deref())?;
+    let format = OutputFormat::resolve(&cli);
+
+    // Offline-first: never block a command on the network for hardware/cloud
+    // data. If the cache looks stale, a refresh happens in the background
+    // while this run still reads whatever's on disk (or bundled). `sync`
+    // already does its own foreground sync below, so skip this to avoid two
+    // writers racing on the same cache file.
+    if !matches!(cli.command, Some(Commands::Sync { .. })) {
+        hf_providers_core::sync::refresh_in_background_if_stale(
+            std::time::Duration::from_secs(24 * 60 * 60),
+            config.sync.base_url.as_deref(),
+        );
+    }
 
     match cli.command {
         Some(Commands::Snippet {
@@ -152,37 +373,34 @@ async fn main() -> anyhow::Result<()> {
             fastest,
             cheapest,
         }) => {
-            cmd_run(&client, &model, &lang, provider.as_deref(), fastest, cheapest).await?;
+            cmd_run(&client, &config, &model, lang.as_deref(), provider.as_deref(), fastest, cheapest).await?;
         }
         Some(Commands::Providers { name, task }) => {
-            cmd_providers(&client, name.as_deref(), task.as_deref()).await?;
+            cmd_providers(&client, format, name.as_deref(), task.as_deref()).await?;
         }
-        Some(Commands::Status { model, watch }) => {
-            cmd_status(&client, &model, watch).await?;
+        Some(Commands::Status { models, watch, log }) => {
+            cmd_status(&client, format, &models, watch, log.as_deref()).await?;
         }
         Some(Commands::Machine { gpu, model }) => {
-            cmd_machine(&client, &gpu, model.as_deref()).await?;
+            cmd_machine(&client, &config, format, &gpu, model.as_deref()).await?;
         }
-        Some(Commands::Need { model }) => {
-            cmd_need(&client, &model).await?;
+        Some(Commands::Need { models, volume }) => {
+            cmd_need(&client, &config, format, &models, volume).await?;
         }
-        Some(Commands::Sync) => {
-            cmd_sync().await?;
+        Some(Commands::Sync { force, ttl }) => {
+            cmd_sync(&config, force, ttl).await?;
         }
         None => {
             if let Some(ref raw) = cli.query {
                 let (model, at_provider, at_lang) = parse_query(raw);
                 if at_provider.is_some() || at_lang.is_some() {
-                    let lang = at_lang
-                        .as_deref()
-                        .unwrap_or("python");
-                    cmd_run(&client, &model, lang, at_provider.as_deref(), false, false)
+                    cmd_run(&client, &config, &model, at_lang.as_deref(), at_provider.as_deref(), false, false)
                         .await?;
                 } else {
                     cmd_search(&client, &model, &cli).await?;
                 }
             } else {
-                cmd_trending(&client).await?;
+                cmd_trending(&client, format).await?;
             }
         }
     }
@@ -216,7 +434,7 @@ fn parse_query(raw: &str) -> (String, Option<String>, Option<String>) {
 
 // ── Trending ─────────────────────────────────────────────────────────
 
-async fn cmd_trending(client: &HfClient) -> anyhow::Result<()> {
+async fn cmd_trending(client: &HfClient, format: OutputFormat) -> anyhow::Result<()> {
     let term = Term::stderr();
     term.write_line(&format!("{}", s_dim().apply_to("loading...")))?;
 
@@ -235,6 +453,22 @@ async fn cmd_trending(client: &HfClient) -> anyhow::Result<()> {
         return Ok(());
     }
 
+    if format.is_machine() {
+        let rows: Vec<Vec<String>> = models
+            .iter()
+            .map(|m| {
+                vec![
+                    m.id.clone(),
+                    m.pipeline_tag.clone().unwrap_or_default(),
+                    m.providers.len().to_string(),
+                    Model::param_hint(&m.id).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_rows(format, &["Id", "Tag", "Providers", "Params"], &rows);
+        return Ok(());
+    }
+
     println!();
     println!("{}", s_header().apply_to("trending models"));
     println!("{}", sep(64));
@@ -369,6 +603,11 @@ async fn cmd_search(client: &HfClient, query: &str, opts: &Cli) -> anyhow::Resul
         .filter(|m| m.id != model.id)
         .collect();
 
+    if OutputFormat::resolve(opts) == OutputFormat::Dot {
+        println!("{}", model_dot(&model, &variants));
+        return Ok(());
+    }
+
     print_model_full(&model, &variants, opts);
 
     // Interactive picker (TTY only, not --json, not piped).
@@ -384,8 +623,9 @@ async fn cmd_search(client: &HfClient, query: &str, opts: &Cli) -> anyhow::Resul
 
 async fn cmd_run(
     client: &HfClient,
+    config: &AppConfig,
     query: &str,
-    lang: &str,
+    lang: Option<&str>,
     provider: Option<&str>,
     fastest: bool,
     cheapest: bool,
@@ -404,23 +644,24 @@ async fn cmd_run(
     let model =
         parse_model(&data).ok_or_else(|| anyhow::anyhow!("could not parse model data"))?;
 
+    let provider = config.resolve_provider(provider);
+    let selection = config.resolve_selection(fastest, cheapest);
+
     let chosen = if let Some(name) = provider {
         model.providers.iter().find(|p| p.name == name)
-    } else if fastest {
+    } else if selection == Selection::Fastest {
         model.fastest()
-    } else if cheapest {
-        model.cheapest()
     } else {
         model.cheapest().or(model.providers.first())
     };
 
     let prov = chosen.ok_or_else(|| anyhow::anyhow!("no providers available"))?;
 
-    let l = Lang::from_str(lang).unwrap_or(Lang::Python);
+    let l = Lang::from_str(config.resolve_lang(lang)).unwrap_or(Lang::Python);
 
     let label = if provider.is_some() {
         "selected"
-    } else if fastest {
+    } else if selection == Selection::Fastest {
         "fastest"
     } else {
         "cheapest"
@@ -439,6 +680,7 @@ async fn cmd_run(
 
 async fn cmd_providers(
     client: &HfClient,
+    format: OutputFormat,
     name: Option<&str>,
     task: Option<&str>,
 ) -> anyhow::Result<()> {
@@ -447,6 +689,21 @@ async fn cmd_providers(
             let results = client.models_by_provider(prov, task, 20).await?;
             let models: Vec<Model> = results.iter().filter_map(parse_model).collect();
 
+            if format.is_machine() {
+                let rows: Vec<Vec<String>> = models
+                    .iter()
+                    .map(|m| {
+                        vec![
+                            m.id.clone(),
+                            m.pipeline_tag.clone().unwrap_or_default(),
+                            m.likes.to_string(),
+                        ]
+                    })
+                    .collect();
+                print_rows(format, &["Model", "Task", "Likes"], &rows);
+                return Ok(());
+            }
+
             let p = PROVIDERS.iter().find(|p| p.id == prov);
             let display = p.map(|p| p.display_name).unwrap_or(prov);
             let kind = p
@@ -470,7 +727,7 @@ async fn cmd_providers(
                     "  {:<45} {:<18} {}",
                     s_bold().apply_to(&m.id),
                     s_dim().apply_to(tag),
-                    s_dim().apply_to(format!("\u{2665} {}", fmt_count(m.likes)))
+                    s_dim().apply_to(format!("{} {}", heart(), fmt_count(m.likes)))
                 );
             }
 
@@ -485,6 +742,21 @@ async fn cmd_providers(
             println!();
         }
         None => {
+            if format.is_machine() {
+                let rows: Vec<Vec<String>> = PROVIDERS
+                    .iter()
+                    .map(|p| {
+                        let kind = match p.kind {
+                            ProviderKind::InferenceProvider => "serverless GPU",
+                            ProviderKind::HfInference => "HF CPU",
+                        };
+                        vec![p.id.to_string(), p.display_name.to_string(), kind.to_string()]
+                    })
+                    .collect();
+                print_rows(format, &["Id", "Name", "Kind"], &rows);
+                return Ok(());
+            }
+
             println!();
             println!("{}", s_header().apply_to("inference providers"));
             println!("{}", sep(64));
@@ -516,20 +788,124 @@ async fn cmd_providers(
     Ok(())
 }
 
+// ── Multi-model fetch ────────────────────────────────────────────────
+
+/// Resolve a single query, falling back from the detail endpoint to search.
+async fn resolve_model(client: &HfClient, query: &str) -> anyhow::Result<Model> {
+    let model = match client.model_info(query).await {
+        Ok(data) => parse_model(&data),
+        Err(_) => {
+            let results = client.search_models(query, 5).await?;
+            results.iter().find_map(parse_model)
+        }
+    };
+    model.ok_or_else(|| anyhow::anyhow!("model not found: {query}"))
+}
+
+/// Fetch several models concurrently, preserving input order. A failure on
+/// one model doesn't abort the others.
+async fn fetch_models_concurrent<'a>(
+    client: &HfClient,
+    queries: &'a [String],
+) -> Vec<(&'a str, anyhow::Result<Model>)> {
+    let futs = queries
+        .iter()
+        .map(|q| async move { (q.as_str(), resolve_model(client, q).await) });
+    join_all(futs).await
+}
+
+/// Open `path` for append, writing the CSV header only if the file is new
+/// or empty.
+fn open_status_log(path: &std::path::Path) -> anyhow::Result<std::fs::File> {
+    let needs_header = !path.exists()
+        || std::fs::metadata(path).map(|m| m.len() == 0).unwrap_or(true);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    if needs_header {
+        writeln!(file, "time,model,provider,readiness,latency_s")?;
+        file.flush()?;
+    }
+    Ok(file)
+}
+
+/// Append one row per provider per polled model, flushing so a `tail -f`
+/// stays current.
+fn append_status_log(
+    file: &mut std::fs::File,
+    results: &[(&str, anyhow::Result<Model>)],
+) -> anyhow::Result<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    for (query, result) in results {
+        match result {
+            Ok(model) => {
+                for p in &model.providers {
+                    writeln!(
+                        file,
+                        "{now},{},{},{:?},{}",
+                        model.id,
+                        p.name,
+                        p.readiness(),
+                        p.latency_s.map(|l| l.to_string()).unwrap_or_default()
+                    )?;
+                }
+            }
+            Err(e) => {
+                writeln!(file, "{now},{query},,error: {e},")?;
+            }
+        }
+    }
+    file.flush()?;
+    Ok(())
+}
+
 // ── Status ───────────────────────────────────────────────────────────
 
 async fn cmd_status(
     client: &HfClient,
-    query: &str,
+    format: OutputFormat,
+    queries: &[String],
     watch: Option<u64>,
+    log: Option<&std::path::Path>,
 ) -> anyhow::Result<()> {
     let pulse = ['\u{2731}', '\u{2726}', '\u{00b7}', '\u{2726}'];
     let mut frame: usize = 0;
+    let mut log_writer = log.map(open_status_log).transpose()?;
 
     loop {
-        let data = client.model_info(query).await?;
-        let model =
-            parse_model(&data).ok_or_else(|| anyhow::anyhow!("could not parse model"))?;
+        let results = fetch_models_concurrent(client, queries).await;
+
+        if let Some(writer) = &mut log_writer {
+            append_status_log(writer, &results)?;
+        }
+
+        if format.is_machine() {
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            for (query, result) in &results {
+                match result {
+                    Ok(model) => {
+                        for p in &model.providers {
+                            rows.push(vec![
+                                model.id.clone(),
+                                p.name.clone(),
+                                format!("{:?}", p.readiness()),
+                                p.latency_s.map(|l| l.to_string()).unwrap_or_default(),
+                            ]);
+                        }
+                    }
+                    Err(e) => {
+                        rows.push(vec![query.to_string(), String::new(), format!("error: {e}"), String::new()]);
+                    }
+                }
+            }
+            print_rows(format, &["Model", "Provider", "Status", "LatencySeconds"], &rows);
+            if watch.is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(watch.unwrap())).await;
+            continue;
+        }
 
         let term = Term::stderr();
         if watch.is_some() {
@@ -546,37 +922,53 @@ async fn cmd_status(
         };
 
         let now = chrono::Local::now().format("%H:%M:%S");
-        println!();
-        println!(
-            "{}  {}{}",
-            s_bold().apply_to(&model.id),
-            s_dim().apply_to(now),
-            refresh
-        );
-        println!("{}", sep(64));
 
-        for p in &model.providers {
-            let r = p.readiness();
-            let ttft = p
-                .latency_s
-                .map(|l| format!("~{:.0}ms TTFT", l * 1000.0))
-                .unwrap_or_else(|| {
-                    if r == Readiness::Cold {
-                        "unavailable".to_string()
-                    } else {
-                        "\u{2500}".to_string()
-                    }
-                });
+        for (query, result) in &results {
+            let model = match result {
+                Ok(m) => m,
+                Err(e) => {
+                    println!();
+                    println!(
+                        "{}  {}",
+                        s_bold().apply_to(query),
+                        s_err().apply_to(format!("error: {e}"))
+                    );
+                    continue;
+                }
+            };
 
+            println!();
             println!(
-                "  {:<16} {:<12} {}",
-                s_accent().apply_to(&p.name),
-                readiness_str(r),
-                s_dim().apply_to(ttft)
+                "{}  {}{}",
+                s_bold().apply_to(&model.id),
+                s_dim().apply_to(now),
+                refresh
             );
-        }
+            println!("{}", sep(64));
 
-        println!("{}", sep(64));
+            for p in &model.providers {
+                let r = p.readiness();
+                let ttft = p
+                    .latency_s
+                    .map(|l| format!("~{:.0}ms TTFT", l * 1000.0))
+                    .unwrap_or_else(|| {
+                        if r == Readiness::Cold {
+                            "unavailable".to_string()
+                        } else {
+                            dash().to_string()
+                        }
+                    });
+
+                println!(
+                    "  {:<16} {:<12} {}",
+                    s_accent().apply_to(&p.name),
+                    readiness_str(r),
+                    s_dim().apply_to(ttft)
+                );
+            }
+
+            println!("{}", sep(64));
+        }
 
         match watch {
             Some(secs) => {
@@ -598,8 +990,15 @@ async fn cmd_status(
 
 // ── Machine ──────────────────────────────────────────────────────────
 
-async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>) -> anyhow::Result<()> {
-    let gpus = hardware::load_hardware_cached()?;
+async fn cmd_machine(
+    client: &HfClient,
+    config: &AppConfig,
+    format: OutputFormat,
+    input: &str,
+    model_query: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut gpus = hardware::load_hardware_cached()?;
+    config.apply_gpu_overrides(&mut gpus);
     let (key, gpu) = hardware::find_gpu(&gpus, input)
         .ok_or_else(|| anyhow::anyhow!("no GPU matching '{input}' in hardware database"))?;
 
@@ -639,7 +1038,7 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
     if let Some(usd) = gpu.street_usd {
         cost_parts.push(format!("street: ~${usd}"));
     }
-    let elec_mo = gpu.tdp_w as f64 * 0.80 * 730.0 / 1000.0 * 0.12;
+    let elec_mo = config.electricity.monthly_cost(gpu.tdp_w);
     cost_parts.push(format!("elec: ~${:.0}/mo", elec_mo));
     println!(
         "  {}",
@@ -688,7 +1087,7 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
     let multi_rt = runtimes.len() > 1;
 
     let fmt_toks = |v: Option<f64>| -> String {
-        let dash = "\u{2500}";
+        let dash = dash();
         match v {
             Some(t) if t >= 1000.0 => format!("{:.1}k t/s", t / 1000.0),
             Some(t) if t >= 1.0 => format!("{:.0} t/s", t),
@@ -700,13 +1099,105 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
 
     let decode_color = |v: Option<f64>| -> Color {
         match v {
-            Some(t) if t >= 30.0 => Color::AnsiValue(114),
-            Some(t) if t >= 10.0 => Color::AnsiValue(214),
-            Some(_) => Color::AnsiValue(208),
-            None => Color::AnsiValue(245),
+            Some(t) if t >= 30.0 => tcolor(114),
+            Some(t) if t >= 10.0 => tcolor(214),
+            Some(_) => tcolor(208),
+            None => tcolor(245),
         }
     };
 
+    if format == OutputFormat::Json && model_query.is_none() {
+        #[derive(Serialize)]
+        struct FitEntryJson {
+            model: String,
+            quant: String,
+            decode_tok_s: Option<f64>,
+            prefill_tok_s: Option<f64>,
+        }
+
+        #[derive(Serialize)]
+        struct FitBucketsJson {
+            runtime: String,
+            comfortable: Vec<FitEntryJson>,
+            tight: Vec<FitEntryJson>,
+            wont_run: Vec<String>,
+        }
+
+        let mut buckets: Vec<FitBucketsJson> = Vec::new();
+        for &rt in &runtimes {
+            let mut comfortable = Vec::new();
+            let mut tight = Vec::new();
+            let mut wont_run = Vec::new();
+
+            for entry in &entries {
+                match estimate::best_quant(&gpu, entry.params, rt, &estimate::ContextSpec::default()) {
+                    Some((q, est)) => {
+                        let is_full = est.fit == Fit::Full;
+                        let fast_decode = est.decode_tok_s.map(|d| d >= 30.0).unwrap_or(false);
+                        let fit_entry = FitEntryJson {
+                            model: entry.short.clone(),
+                            quant: q.label().to_string(),
+                            decode_tok_s: est.decode_tok_s,
+                            prefill_tok_s: est.prefill_tok_s,
+                        };
+                        if is_full && fast_decode {
+                            comfortable.push(fit_entry);
+                        } else {
+                            tight.push(fit_entry);
+                        }
+                    }
+                    None => wont_run.push(entry.short.clone()),
+                }
+            }
+
+            buckets.push(FitBucketsJson {
+                runtime: rt.to_string(),
+                comfortable,
+                tight,
+                wont_run,
+            });
+        }
+
+        println!("{}", serde_json::to_string_pretty(&buckets).unwrap_or_default());
+        return Ok(());
+    }
+
+    if format.is_machine() {
+        let fit_raw = |fit: &Fit| -> String {
+            match fit {
+                Fit::Full => "full".to_string(),
+                Fit::Partial { gpu_layers, cpu_layers } => {
+                    format!("partial:{gpu_layers}/{}", gpu_layers + cpu_layers)
+                }
+                Fit::NoFit => "none".to_string(),
+            }
+        };
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for entry in &entries {
+            for &rt in &runtimes {
+                if let Some((q, est)) =
+                    estimate::best_quant(&gpu, entry.params, rt, &estimate::ContextSpec::default())
+                {
+                    rows.push(vec![
+                        entry.short.clone(),
+                        rt.to_string(),
+                        q.label().to_string(),
+                        format!("{:.1}", est.weight_gb),
+                        fit_raw(&est.fit),
+                        est.decode_tok_s.unwrap_or(0.0).to_string(),
+                        est.prefill_tok_s.unwrap_or(0.0).to_string(),
+                    ]);
+                }
+            }
+        }
+        print_rows(
+            format,
+            &["Model", "Runtime", "Quant", "Weight", "Fit", "Decode", "Prefill"],
+            &rows,
+        );
+        return Ok(());
+    }
+
     // Single model mode: show per-runtime rows in one table.
     if model_query.is_some() {
         println!();
@@ -720,37 +1211,41 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
         table.load_preset(presets::NOTHING);
         table.set_content_arrangement(ContentArrangement::Dynamic);
         let mut header = vec![
-            Cell::new("  Quant").fg(Color::AnsiValue(243)),
-            Cell::new("Weight").fg(Color::AnsiValue(243)),
-            Cell::new("Fit").fg(Color::AnsiValue(243)),
-            Cell::new("Decode").fg(Color::AnsiValue(243)),
-            Cell::new("Prefill").fg(Color::AnsiValue(243)),
+            Cell::new("  Quant").fg(tcolor(243)),
+            Cell::new("Weight").fg(tcolor(243)),
+            Cell::new("Fit").fg(tcolor(243)),
+            Cell::new("Decode").fg(tcolor(243)),
+            Cell::new("Prefill").fg(tcolor(243)),
         ];
         if multi_rt {
-            header.insert(0, Cell::new("  Runtime").fg(Color::AnsiValue(243)));
+            header.insert(0, Cell::new("  Runtime").fg(tcolor(243)));
         }
         table.set_header(header);
 
         let mut has_rows = false;
         for &rt in &runtimes {
-            if let Some((q, est)) = estimate::best_quant(&gpu, entries[0].params, rt) {
+            if let Some((q, est)) = estimate::best_quant(&gpu, entries[0].params, rt, &estimate::ContextSpec::default()) {
                 let fit_str = match &est.fit {
                     Fit::Full => "fits in VRAM".to_string(),
+                    Fit::Partial { gpu_layers, cpu_layers } => {
+                        format!("partial ({gpu_layers}/{} layers)", gpu_layers + cpu_layers)
+                    }
                     Fit::NoFit => "does not fit".to_string(),
                 };
                 let fit_c = match &est.fit {
-                    Fit::Full => Color::AnsiValue(114),
-                    Fit::NoFit => Color::AnsiValue(167),
+                    Fit::Full => tcolor(114),
+                    Fit::Partial { .. } => tcolor(179),
+                    Fit::NoFit => tcolor(167),
                 };
                 let mut row = vec![
-                    Cell::new(format!("  {}", q.label())).fg(Color::AnsiValue(248)),
-                    Cell::new(format!("{:.0} GB", est.weight_gb)).fg(Color::AnsiValue(248)),
+                    Cell::new(format!("  {}", q.label())).fg(tcolor(248)),
+                    Cell::new(format!("{:.0} GB", est.weight_gb)).fg(tcolor(248)),
                     Cell::new(&fit_str).fg(fit_c),
                     Cell::new(fmt_toks(est.decode_tok_s)).fg(decode_color(est.decode_tok_s)),
-                    Cell::new(fmt_toks(est.prefill_tok_s)).fg(Color::AnsiValue(248)),
+                    Cell::new(fmt_toks(est.prefill_tok_s)).fg(tcolor(248)),
                 ];
                 if multi_rt {
-                    row.insert(0, Cell::new(format!("  {rt}")).fg(Color::AnsiValue(109)));
+                    row.insert(0, Cell::new(format!("  {rt}")).fg(tcolor(109)));
                 }
                 table.add_row(row);
                 has_rows = true;
@@ -779,7 +1274,7 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
             let mut wont_run: Vec<String> = Vec::new();
 
             for entry in &entries {
-                match estimate::best_quant(&gpu, entry.params, rt) {
+                match estimate::best_quant(&gpu, entry.params, rt, &estimate::ContextSpec::default()) {
                     Some((q, est)) => {
                         let is_full = est.fit == Fit::Full;
                         let fast_decode = est.decode_tok_s.map(|d| d >= 30.0).unwrap_or(false);
@@ -809,18 +1304,18 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
                 table.load_preset(presets::NOTHING);
                 table.set_content_arrangement(ContentArrangement::Dynamic);
                 table.set_header(vec![
-                    Cell::new("  Model").fg(Color::AnsiValue(243)),
-                    Cell::new("Quant").fg(Color::AnsiValue(243)),
-                    Cell::new("Decode").fg(Color::AnsiValue(243)),
-                    Cell::new("Prefill").fg(Color::AnsiValue(243)),
+                    Cell::new("  Model").fg(tcolor(243)),
+                    Cell::new("Quant").fg(tcolor(243)),
+                    Cell::new("Decode").fg(tcolor(243)),
+                    Cell::new("Prefill").fg(tcolor(243)),
                 ]);
 
                 for r in &comfortable {
                     table.add_row(vec![
-                        Cell::new(format!("  {}", r.short)).fg(Color::AnsiValue(252)),
-                        Cell::new(&r.quant).fg(Color::AnsiValue(248)),
+                        Cell::new(format!("  {}", r.short)).fg(tcolor(252)),
+                        Cell::new(&r.quant).fg(tcolor(248)),
                         Cell::new(fmt_toks(r.decode)).fg(decode_color(r.decode)),
-                        Cell::new(fmt_toks(r.prefill)).fg(Color::AnsiValue(248)),
+                        Cell::new(fmt_toks(r.prefill)).fg(tcolor(248)),
                     ]);
                 }
                 println!("{table}");
@@ -834,18 +1329,18 @@ async fn cmd_machine(client: &HfClient, input: &str, model_query: Option<&str>)
                 table.load_preset(presets::NOTHING);
                 table.set_content_arrangement(ContentArrangement::Dynamic);
                 table.set_header(vec![
-                    Cell::new("  Model").fg(Color::AnsiValue(243)),
-                    Cell::new("Quant").fg(Color::AnsiValue(243)),
-                    Cell::new("Decode").fg(Color::AnsiValue(243)),
-                    Cell::new("Prefill").fg(Color::AnsiValue(243)),
+                    Cell::new("  Model").fg(tcolor(243)),
+                    Cell::new("Quant").fg(tcolor(243)),
+                    Cell::new("Decode").fg(tcolor(243)),
+                    Cell::new("Prefill").fg(tcolor(243)),
                 ]);
 
                 for r in &tight {
                     table.add_row(vec![
-                        Cell::new(format!("  {}", r.short)).fg(Color::AnsiValue(252)),
-                        Cell::new(&r.quant).fg(Color::AnsiValue(248)),
+                        Cell::new(format!("  {}", r.short)).fg(tcolor(252)),
+                        Cell::new(&r.quant).fg(tcolor(248)),
                         Cell::new(fmt_toks(r.decode)).fg(decode_color(r.decode)),
-                        Cell::new(fmt_toks(r.prefill)).fg(Color::AnsiValue(248)),
+                        Cell::new(fmt_toks(r.prefill)).fg(tcolor(248)),
                     ]);
                 }
                 println!("{table}");
@@ -889,21 +1384,173 @@ fn fmt_cost(v: f64) -> String {
     }
 }
 
-async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
-    // 1. Resolve model.
-    let term = Term::stderr();
-    term.write_line(&format!("{}", s_dim().apply_to("resolving model...")))?;
+async fn cmd_need(
+    client: &HfClient,
+    config: &AppConfig,
+    format: OutputFormat,
+    queries: &[String],
+    volume: Option<f64>,
+) -> anyhow::Result<()> {
+    if queries.len() == 1 {
+        let term = Term::stderr();
+        term.write_line(&format!("{}", s_dim().apply_to("resolving model...")))?;
+        let model = resolve_model(client, &queries[0]).await;
+        term.clear_last_lines(1)?;
+        return cmd_need_single(config, format, model?, volume).await;
+    }
 
-    let model = match client.model_info(query).await {
-        Ok(data) => parse_model(&data),
-        Err(_) => {
-            let results = client.search_models(query, 5).await?;
-            results.iter().find_map(parse_model)
+    cmd_need_compare(client, config, format, queries).await
+}
+
+/// Side-by-side comparison of cheapest API / cloud / local cost across
+/// several models, for `need` invoked with more than one model.
+async fn cmd_need_compare(
+    client: &HfClient,
+    config: &AppConfig,
+    format: OutputFormat,
+    queries: &[String],
+) -> anyhow::Result<()> {
+    let results = fetch_models_concurrent(client, queries).await;
+    let mut gpus = hardware::load_hardware_cached()?;
+    config.apply_gpu_overrides(&mut gpus);
+    let offerings = cloud::load_cloud_cached()?;
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+
+    for (query, result) in &results {
+        let model = match result {
+            Ok(m) => m,
+            Err(e) => {
+                rows.push(vec![
+                    query.to_string(),
+                    String::new(),
+                    format!("error: {e}"),
+                    String::new(),
+                    String::new(),
+                ]);
+                continue;
+            }
+        };
+
+        let short = model.id.rsplit('/').next().unwrap_or(&model.id).to_string();
+        let params = match model.estimated_params().or_else(|| {
+            REFERENCE_MODELS
+                .iter()
+                .find(|rm| rm.id == model.id)
+                .map(|rm| rm.params)
+        }) {
+            Some(p) => p,
+            None => {
+                rows.push(vec![
+                    short,
+                    String::new(),
+                    "error: unknown param count".to_string(),
+                    String::new(),
+                    String::new(),
+                ]);
+                continue;
+            }
+        };
+
+        let cheapest_api = model
+            .providers
+            .iter()
+            .filter_map(|p| p.output_price_per_m)
+            .fold(f64::INFINITY, f64::min);
+
+        let runtime = config.resolve_runtime();
+        let cheapest_cloud = offerings
+            .iter()
+            .filter_map(|(_key, offering)| {
+                let gpu = gpus.iter().find(|(k, _)| *k == offering.gpu).map(|(_, g)| g)?;
+                let result = if offering.gpu_count > 1 {
+                    estimate::best_quant_multi_gpu(gpu, params, runtime, offering.gpu_count, &estimate::ContextSpec::default())
+                } else {
+                    estimate::best_quant(gpu, params, runtime, &estimate::ContextSpec::default())
+                };
+                let (_, est) = result?;
+                let tok_s = est.decode_tok_s.unwrap_or(0.0);
+                if tok_s <= 0.0 {
+                    return None;
+                }
+                let total_hr = offering.price_hr * offering.gpu_count as f64;
+                Some(cost_per_m(total_hr, tok_s) / config.cost_model.cloud_utilization_pct)
+            })
+            .fold(f64::INFINITY, f64::min);
+
+        let cheapest_local = gpus
+            .iter()
+            .filter_map(|(_, gpu)| {
+                gpu.available_runtimes().iter().filter_map(|&rt| {
+                    let (_, est) = estimate::best_quant(gpu, params, rt, &estimate::ContextSpec::default())?;
+                    let tok_s = est.decode_tok_s.unwrap_or(0.0);
+                    if tok_s <= 0.0 {
+                        return None;
+                    }
+                    let elec_hr = config.electricity.hourly_cost(gpu.tdp_w);
+                    Some(cost_per_m(elec_hr, tok_s))
+                }).fold(None, |best: Option<f64>, v| match best {
+                    Some(b) if b <= v => Some(b),
+                    _ => Some(v),
+                })
+            })
+            .fold(f64::INFINITY, f64::min);
+
+        rows.push(vec![
+            short,
+            Model::fmt_params(params),
+            cheapest_api.is_finite().then(|| fmt_cost(cheapest_api)).unwrap_or_else(|| dash().to_string()),
+            cheapest_cloud.is_finite().then(|| fmt_cost(cheapest_cloud)).unwrap_or_else(|| dash().to_string()),
+            cheapest_local.is_finite().then(|| fmt_cost(cheapest_local)).unwrap_or_else(|| dash().to_string()),
+        ]);
+    }
+
+    let headers = ["Model", "Params", "CheapestApi", "CheapestCloud", "CheapestLocal"];
+
+    if format.is_machine() {
+        print_rows(format, &headers, &rows);
+        return Ok(());
+    }
+
+    println!();
+    println!("  {}", s_header().apply_to("cheapest $/1M output tokens"));
+    println!("  {}", sep(72));
+
+    if is_basic() {
+        print_basic_rows(&rows);
+    } else {
+        let mut table = Table::new();
+        table.load_preset(presets::NOTHING);
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("  Model").fg(tcolor(243)),
+            Cell::new("Params").fg(tcolor(243)),
+            Cell::new("API").fg(tcolor(243)),
+            Cell::new("Cloud GPU").fg(tcolor(243)),
+            Cell::new("Local GPU").fg(tcolor(243)),
+        ]);
+        for row in &rows {
+            table.add_row(vec![
+                Cell::new(format!("  {}", row[0])).fg(tcolor(252)),
+                Cell::new(&row[1]).fg(tcolor(248)),
+                Cell::new(&row[2]).fg(tcolor(109)),
+                Cell::new(&row[3]).fg(tcolor(109)),
+                Cell::new(&row[4]).fg(tcolor(109)),
+            ]);
         }
-    };
-    term.clear_last_lines(1)?;
+        println!("{table}");
+    }
+    println!();
 
-    let model = model.ok_or_else(|| anyhow::anyhow!("model not found: {query}"))?;
+    Ok(())
+}
+
+async fn cmd_need_single(
+    config: &AppConfig,
+    format: OutputFormat,
+    model: Model,
+    volume: Option<f64>,
+) -> anyhow::Result<()> {
     let params = model
         .estimated_params()
         .or_else(|| {
@@ -924,13 +1571,15 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
     let weight_q4 = params as f64 * 0.5 / 1e9;
 
     // Header.
-    println!();
-    println!(
-        "  {}  {}  {}",
-        s_header().apply_to(short),
-        s_param().apply_to(Model::fmt_params(params)),
-        s_dim().apply_to(format!("Q4 = {:.0} GB", weight_q4)),
-    );
+    if !format.is_machine() {
+        println!();
+        println!(
+            "  {}  {}  {}",
+            s_header().apply_to(short),
+            s_param().apply_to(Model::fmt_params(params)),
+            s_dim().apply_to(format!("Q4 = {:.0} GB", weight_q4)),
+        );
+    }
 
     // ── API providers ────────────────────────────────────────────────
     let api_providers: Vec<&ProviderInfo> = model
@@ -944,54 +1593,79 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
         .filter_map(|p| p.output_price_per_m)
         .fold(f64::INFINITY, f64::min);
 
-    if !api_providers.is_empty() {
-        println!();
-        println!("  {}", s_header().apply_to("api providers"));
-        println!("  {}", sep(48));
+    let mut sorted: Vec<&&ProviderInfo> = api_providers.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.output_price_per_m
+            .unwrap()
+            .partial_cmp(&b.output_price_per_m.unwrap())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
 
-        let mut table = Table::new();
-        table.load_preset(presets::NOTHING);
-        table.set_content_arrangement(ContentArrangement::Dynamic);
-        table.set_header(vec![
-            Cell::new("  Provider").fg(Color::AnsiValue(243)),
-            Cell::new("Status").fg(Color::AnsiValue(243)),
-            Cell::new("$/1M in").fg(Color::AnsiValue(243)),
-            Cell::new("$/1M out").fg(Color::AnsiValue(243)),
-        ]);
+    let api_rows: Vec<Vec<String>> = sorted
+        .iter()
+        .map(|p| {
+            vec![
+                p.name.clone(),
+                format!("{:?}", p.readiness()),
+                p.input_price_per_m.map(|v| v.to_string()).unwrap_or_default(),
+                p.output_price_per_m.unwrap().to_string(),
+            ]
+        })
+        .collect();
 
-        let mut sorted: Vec<&&ProviderInfo> = api_providers.iter().collect();
-        sorted.sort_by(|a, b| {
-            a.output_price_per_m
-                .unwrap()
-                .partial_cmp(&b.output_price_per_m.unwrap())
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    if !format.is_machine() {
+        if !api_providers.is_empty() {
+            println!();
+            println!("  {}", s_header().apply_to("api providers"));
+            println!("  {}", sep(48));
 
-        for p in &sorted {
-            let in_price = p
-                .input_price_per_m
-                .map(fmt_cost)
-                .unwrap_or_else(|| "\u{2500}".to_string());
-            let out_price = fmt_cost(p.output_price_per_m.unwrap());
-            let rd = p.readiness();
-            table.add_row(vec![
-                Cell::new(format!("  {}", p.name)).fg(Color::AnsiValue(252)),
-                Cell::new(readiness_str(rd)),
-                Cell::new(&in_price).fg(Color::AnsiValue(109)),
-                Cell::new(&out_price).fg(Color::AnsiValue(109)),
-            ]);
+            let api_display_rows: Vec<Vec<String>> = sorted
+                .iter()
+                .map(|p| {
+                    let in_price = p
+                        .input_price_per_m
+                        .map(fmt_cost)
+                        .unwrap_or_else(|| dash().to_string());
+                    let out_price = fmt_cost(p.output_price_per_m.unwrap());
+                    vec![p.name.clone(), readiness_str(p.readiness()), in_price, out_price]
+                })
+                .collect();
+
+            if is_basic() {
+                print_basic_rows(&api_display_rows);
+            } else {
+                let mut table = Table::new();
+                table.load_preset(presets::NOTHING);
+                table.set_content_arrangement(ContentArrangement::Dynamic);
+                table.set_header(vec![
+                    Cell::new("  Provider").fg(tcolor(243)),
+                    Cell::new("Status").fg(tcolor(243)),
+                    Cell::new("$/1M in").fg(tcolor(243)),
+                    Cell::new("$/1M out").fg(tcolor(243)),
+                ]);
+
+                for row in &api_display_rows {
+                    table.add_row(vec![
+                        Cell::new(format!("  {}", row[0])).fg(tcolor(252)),
+                        Cell::new(&row[1]),
+                        Cell::new(&row[2]).fg(tcolor(109)),
+                        Cell::new(&row[3]).fg(tcolor(109)),
+                    ]);
+                }
+                println!("{table}");
+            }
+        } else {
+            println!();
+            println!(
+                "  {}",
+                s_dim().apply_to("no api providers with pricing found")
+            );
         }
-        println!("{table}");
-    } else {
-        println!();
-        println!(
-            "  {}",
-            s_dim().apply_to("no api providers with pricing found")
-        );
     }
 
     // ── Cloud GPU ────────────────────────────────────────────────────
-    let gpus = hardware::load_hardware_cached()?;
+    let mut gpus = hardware::load_hardware_cached()?;
+    config.apply_gpu_overrides(&mut gpus);
     let offerings = cloud::load_cloud_cached()?;
 
     struct CloudRow {
@@ -1012,10 +1686,11 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
             None => continue,
         };
 
+        let runtime = config.resolve_runtime();
         let result = if offering.gpu_count > 1 {
-            estimate::best_quant_multi_gpu(gpu, params, Runtime::LlamaCpp, offering.gpu_count)
+            estimate::best_quant_multi_gpu(gpu, params, runtime, offering.gpu_count, &estimate::ContextSpec::default())
         } else {
-            estimate::best_quant(gpu, params, Runtime::LlamaCpp)
+            estimate::best_quant(gpu, params, runtime, &estimate::ContextSpec::default())
         };
 
         if let Some((q, est)) = result {
@@ -1024,7 +1699,7 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
                 continue;
             }
             let total_hr = offering.price_hr * offering.gpu_count as f64;
-            let eff = cost_per_m(total_hr, tok_s);
+            let eff = cost_per_m(total_hr, tok_s) / config.cost_model.cloud_utilization_pct;
             cloud_rows.push(CloudRow {
                 name: offering.name.clone(),
                 provider: offering.provider.clone(),
@@ -1039,7 +1714,26 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
 
     cloud_rows.sort_by(|a, b| a.eff_cost.partial_cmp(&b.eff_cost).unwrap());
 
-    if !cloud_rows.is_empty() {
+    let cloud_machine_rows: Vec<Vec<String>> = cloud_rows
+        .iter()
+        .map(|r| {
+            let gpu_label = if r.gpu_count > 1 {
+                format!("{}x {}", r.gpu_count, r.name)
+            } else {
+                r.name.clone()
+            };
+            vec![
+                gpu_label,
+                r.provider.clone(),
+                r.total_hr.to_string(),
+                r.quant.clone(),
+                r.tok_s.to_string(),
+                r.eff_cost.to_string(),
+            ]
+        })
+        .collect();
+
+    if !format.is_machine() && !cloud_rows.is_empty() {
         println!();
         println!(
             "  {}",
@@ -1047,37 +1741,57 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
         );
         println!(
             "  {}",
-            s_dim().apply_to("floor cost at 100% utilization")
+            s_dim().apply_to(format!(
+                "floor cost at {:.0}% utilization",
+                config.cost_model.cloud_utilization_pct * 100.0
+            ))
         );
         println!("  {}", sep(60));
 
-        let mut table = Table::new();
-        table.load_preset(presets::NOTHING);
-        table.set_content_arrangement(ContentArrangement::Dynamic);
-        table.set_header(vec![
-            Cell::new("  Offering").fg(Color::AnsiValue(243)),
-            Cell::new("$/hr").fg(Color::AnsiValue(243)),
-            Cell::new("Quant").fg(Color::AnsiValue(243)),
-            Cell::new("tok/s").fg(Color::AnsiValue(243)),
-            Cell::new("$/1M out").fg(Color::AnsiValue(243)),
-        ]);
+        let cloud_display_rows: Vec<Vec<String>> = cloud_rows
+            .iter()
+            .take(10)
+            .map(|r| {
+                let gpu_label = if r.gpu_count > 1 {
+                    format!("{}x {}", r.gpu_count, r.name)
+                } else {
+                    r.name.clone()
+                };
+                vec![
+                    format!("{gpu_label} ({})", r.provider),
+                    format!("${:.2}/hr", r.total_hr),
+                    r.quant.clone(),
+                    format!("{:.0} tok/s", r.tok_s),
+                    fmt_cost(r.eff_cost),
+                ]
+            })
+            .collect();
 
-        for r in cloud_rows.iter().take(10) {
-            let gpu_label = if r.gpu_count > 1 {
-                format!("{}x {}", r.gpu_count, r.name)
-            } else {
-                r.name.clone()
-            };
-            let label = format!("{} ({})", gpu_label, r.provider);
-            table.add_row(vec![
-                Cell::new(format!("  {label}")).fg(Color::AnsiValue(252)),
-                Cell::new(format!("${:.2}", r.total_hr)).fg(Color::AnsiValue(109)),
-                Cell::new(&r.quant).fg(Color::AnsiValue(248)),
-                Cell::new(format!("{:.0}", r.tok_s)).fg(Color::AnsiValue(248)),
-                Cell::new(fmt_cost(r.eff_cost)).fg(Color::AnsiValue(109)),
+        if is_basic() {
+            print_basic_rows(&cloud_display_rows);
+        } else {
+            let mut table = Table::new();
+            table.load_preset(presets::NOTHING);
+            table.set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_header(vec![
+                Cell::new("  Offering").fg(tcolor(243)),
+                Cell::new("$/hr").fg(tcolor(243)),
+                Cell::new("Quant").fg(tcolor(243)),
+                Cell::new("tok/s").fg(tcolor(243)),
+                Cell::new("$/1M out").fg(tcolor(243)),
             ]);
+
+            for row in &cloud_display_rows {
+                table.add_row(vec![
+                    Cell::new(format!("  {}", row[0])).fg(tcolor(252)),
+                    Cell::new(&row[1]).fg(tcolor(109)),
+                    Cell::new(&row[2]).fg(tcolor(248)),
+                    Cell::new(&row[3]).fg(tcolor(248)),
+                    Cell::new(&row[4]).fg(tcolor(109)),
+                ]);
+            }
+            println!("{table}");
         }
-        println!("{table}");
     }
 
     // ── Local GPU ────────────────────────────────────────────────────
@@ -1090,19 +1804,16 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
         payback_m_tok: Option<f64>,
     }
 
-    let elec_kwh = 0.12_f64;
-    let load_pct = 0.80_f64;
-
     let mut local_rows: Vec<LocalRow> = Vec::new();
 
     for (_key, gpu) in &gpus {
         for &rt in &gpu.available_runtimes() {
-            if let Some((q, est)) = estimate::best_quant(gpu, params, rt) {
+            if let Some((q, est)) = estimate::best_quant(gpu, params, rt, &estimate::ContextSpec::default()) {
                 let tok_s = est.decode_tok_s.unwrap_or(0.0);
                 if tok_s <= 0.0 {
                     continue;
                 }
-                let elec_hr = gpu.tdp_w as f64 * load_pct * elec_kwh / 1000.0;
+                let elec_hr = config.electricity.hourly_cost(gpu.tdp_w);
                 let eff = cost_per_m(elec_hr, tok_s);
 
                 let payback = gpu.street_usd.and_then(|usd| {
@@ -1146,6 +1857,128 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
         .take(8)
         .collect();
 
+    let local_machine_rows: Vec<Vec<String>> = local_display
+        .iter()
+        .map(|r| {
+            vec![
+                r.name.clone(),
+                r.street.map(|v| v.to_string()).unwrap_or_default(),
+                r.quant.clone(),
+                r.tok_s.to_string(),
+                r.eff_cost.to_string(),
+                r.payback_m_tok.map(|v| v.to_string()).unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    if format == OutputFormat::Json {
+        #[derive(Serialize)]
+        struct NeedModelJson {
+            id: String,
+            params: u64,
+            q4_gb: f64,
+        }
+
+        #[derive(Serialize)]
+        struct NeedApiProviderJson {
+            name: String,
+            status: String,
+            input_price_per_m: Option<f64>,
+            output_price_per_m: f64,
+        }
+
+        #[derive(Serialize)]
+        struct NeedCloudJson {
+            name: String,
+            provider: String,
+            gpu_count: u32,
+            total_hr: f64,
+            quant: String,
+            tok_s: f64,
+            eff_cost: f64,
+        }
+
+        #[derive(Serialize)]
+        struct NeedLocalJson {
+            name: String,
+            street_usd: Option<u32>,
+            quant: String,
+            tok_s: f64,
+            eff_cost: f64,
+            payback_m_tok: Option<f64>,
+        }
+
+        #[derive(Serialize)]
+        struct NeedReportJson {
+            model: NeedModelJson,
+            api_providers: Vec<NeedApiProviderJson>,
+            cloud: Vec<NeedCloudJson>,
+            local: Vec<NeedLocalJson>,
+        }
+
+        let report = NeedReportJson {
+            model: NeedModelJson {
+                id: model.id.clone(),
+                params,
+                q4_gb: weight_q4,
+            },
+            api_providers: sorted
+                .iter()
+                .map(|p| NeedApiProviderJson {
+                    name: p.name.clone(),
+                    status: format!("{:?}", p.readiness()),
+                    input_price_per_m: p.input_price_per_m,
+                    output_price_per_m: p.output_price_per_m.unwrap(),
+                })
+                .collect(),
+            cloud: cloud_rows
+                .iter()
+                .map(|r| NeedCloudJson {
+                    name: r.name.clone(),
+                    provider: r.provider.clone(),
+                    gpu_count: r.gpu_count,
+                    total_hr: r.total_hr,
+                    quant: r.quant.clone(),
+                    tok_s: r.tok_s,
+                    eff_cost: r.eff_cost,
+                })
+                .collect(),
+            local: local_display
+                .iter()
+                .map(|r| NeedLocalJson {
+                    name: r.name.clone(),
+                    street_usd: r.street,
+                    quant: r.quant.clone(),
+                    tok_s: r.tok_s,
+                    eff_cost: r.eff_cost,
+                    payback_m_tok: r.payback_m_tok,
+                })
+                .collect(),
+        };
+
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+        return Ok(());
+    }
+
+    if format.is_machine() {
+        print_rows(
+            format,
+            &["Provider", "Status", "InputPricePerM", "OutputPricePerM"],
+            &api_rows,
+        );
+        print_rows(
+            format,
+            &["Offering", "Provider", "PricePerHr", "Quant", "TokS", "CostPerMOut"],
+            &cloud_machine_rows,
+        );
+        print_rows(
+            format,
+            &["GPU", "StreetUsd", "Quant", "TokS", "CostPerMOut", "PaybackMTok"],
+            &local_machine_rows,
+        );
+        return Ok(());
+    }
+
     if !local_display.is_empty() {
         println!();
         println!(
@@ -1154,47 +1987,153 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
         );
         println!(
             "  {}",
-            s_dim().apply_to("marginal electricity only, $0.12/kWh, 80% TDP")
+            s_dim().apply_to(format!(
+                "marginal electricity only, ${:.2}/kWh, {:.0}% TDP",
+                config.electricity.price_per_kwh,
+                config.electricity.utilization * 100.0
+            ))
         );
         println!("  {}", sep(64));
 
-        let mut table = Table::new();
-        table.load_preset(presets::NOTHING);
-        table.set_content_arrangement(ContentArrangement::Dynamic);
-        table.set_header(vec![
-            Cell::new("  GPU").fg(Color::AnsiValue(243)),
-            Cell::new("Street").fg(Color::AnsiValue(243)),
-            Cell::new("Quant").fg(Color::AnsiValue(243)),
-            Cell::new("tok/s").fg(Color::AnsiValue(243)),
-            Cell::new("$/1M out").fg(Color::AnsiValue(243)),
-            Cell::new("payback").fg(Color::AnsiValue(243)),
-        ]);
+        let local_rows: Vec<Vec<String>> = local_display
+            .iter()
+            .map(|r| {
+                let street_str = r
+                    .street
+                    .map(|v| format!("${v}"))
+                    .unwrap_or_else(|| dash().to_string());
+                let payback_str = r
+                    .payback_m_tok
+                    .map(|v| {
+                        if v >= 1000.0 {
+                            format!("{:.1}B tok", v / 1000.0)
+                        } else {
+                            format!("{:.0}M tok", v)
+                        }
+                    })
+                    .unwrap_or_else(|| dash().to_string());
+                vec![
+                    r.name.clone(),
+                    street_str,
+                    r.quant.clone(),
+                    format!("{:.0} tok/s", r.tok_s),
+                    fmt_cost(r.eff_cost),
+                    payback_str,
+                ]
+            })
+            .collect();
 
-        for r in &local_display {
-            let street_str = r
-                .street
-                .map(|v| format!("${v}"))
-                .unwrap_or_else(|| "\u{2500}".to_string());
-            let payback_str = r
-                .payback_m_tok
-                .map(|v| {
-                    if v >= 1000.0 {
-                        format!("{:.1}B tok", v / 1000.0)
-                    } else {
-                        format!("{:.0}M tok", v)
-                    }
-                })
-                .unwrap_or_else(|| "\u{2500}".to_string());
-            table.add_row(vec![
-                Cell::new(format!("  {}", r.name)).fg(Color::AnsiValue(252)),
-                Cell::new(&street_str).fg(Color::AnsiValue(109)),
-                Cell::new(&r.quant).fg(Color::AnsiValue(248)),
-                Cell::new(format!("{:.0}", r.tok_s)).fg(Color::AnsiValue(248)),
-                Cell::new(fmt_cost(r.eff_cost)).fg(Color::AnsiValue(109)),
-                Cell::new(&payback_str).fg(Color::AnsiValue(248)),
+        if is_basic() {
+            print_basic_rows(&local_rows);
+        } else {
+            let mut table = Table::new();
+            table.load_preset(presets::NOTHING);
+            table.set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_header(vec![
+                Cell::new("  GPU").fg(tcolor(243)),
+                Cell::new("Street").fg(tcolor(243)),
+                Cell::new("Quant").fg(tcolor(243)),
+                Cell::new("tok/s").fg(tcolor(243)),
+                Cell::new("$/1M out").fg(tcolor(243)),
+                Cell::new("payback").fg(tcolor(243)),
             ]);
+            for row in &local_rows {
+                table.add_row(vec![
+                    Cell::new(format!("  {}", row[0])).fg(tcolor(252)),
+                    Cell::new(&row[1]).fg(tcolor(109)),
+                    Cell::new(&row[2]).fg(tcolor(248)),
+                    Cell::new(&row[3]).fg(tcolor(248)),
+                    Cell::new(&row[4]).fg(tcolor(109)),
+                    Cell::new(&row[5]).fg(tcolor(248)),
+                ]);
+            }
+            println!("{table}");
+        }
+    }
+
+    // ── TCO at a monthly volume ──────────────────────────────────────
+    if let Some(v) = volume {
+        if !format.is_machine() {
+            let amort_months = config.cost_model.amortization_months;
+            let best_cloud = cloud_rows.first();
+            let best_local = local_display.first().copied();
+
+            // Crossover volume (in M tok/mo) where `variable_per_m` plus a
+            // one-time `fixed_cost` (amortized per month) beats the cheapest
+            // API rate. `None` when the option never catches up.
+            let crossover = |fixed_cost: f64, variable_per_m: f64| -> Option<f64> {
+                if cheapest_api_out.is_finite() && variable_per_m < cheapest_api_out {
+                    Some(fixed_cost / (cheapest_api_out - variable_per_m))
+                } else {
+                    None
+                }
+            };
+
+            struct TcoOption {
+                label: String,
+                monthly: f64,
+            }
+            let mut options: Vec<TcoOption> = Vec::new();
+            if cheapest_api_out.is_finite() {
+                options.push(TcoOption {
+                    label: "api".to_string(),
+                    monthly: v * cheapest_api_out,
+                });
+            }
+            if let Some(c) = best_cloud {
+                options.push(TcoOption {
+                    label: format!("cloud: {} ({})", c.name, c.provider),
+                    monthly: v * c.eff_cost,
+                });
+            }
+            if let Some(l) = best_local {
+                if let Some(street) = l.street {
+                    options.push(TcoOption {
+                        label: format!("local: {}", l.name),
+                        monthly: street as f64 / amort_months + v * l.eff_cost,
+                    });
+                }
+            }
+            options.sort_by(|a, b| a.monthly.partial_cmp(&b.monthly).unwrap());
+
+            println!();
+            println!("  {}", s_header().apply_to(format!("tco at {v:.0}M tok/mo")));
+            println!("  {}", sep(64));
+            for (i, opt) in options.iter().enumerate() {
+                let star = if i == 0 { s_hot().apply_to("\u{2605}").to_string() } else { " ".to_string() };
+                println!("  {} {:<28} {}", star, opt.label, fmt_cost(opt.monthly));
+            }
+
+            if let Some(l) = best_local {
+                if let Some(street) = l.street {
+                    match crossover(street as f64 / amort_months, l.eff_cost) {
+                        Some(v_star) => println!(
+                            "{}",
+                            s_hint().apply_to(format!(
+                                "  buy the {} once you exceed {:.0}M tok/mo",
+                                l.name, v_star
+                            ))
+                        ),
+                        None => println!(
+                            "{}",
+                            s_hint().apply_to(format!("  the {} never beats API on cost", l.name))
+                        ),
+                    }
+                }
+            }
+            if let Some(c) = best_cloud {
+                match crossover(0.0, c.eff_cost) {
+                    Some(_) => println!(
+                        "{}",
+                        s_hint().apply_to(format!("  {} beats API at any volume", c.name))
+                    ),
+                    None => println!(
+                        "{}",
+                        s_hint().apply_to(format!("  {} never beats API on cost", c.name))
+                    ),
+                }
+            }
         }
-        println!("{table}");
     }
 
     // Footer.
@@ -1215,6 +2154,58 @@ async fn cmd_need(client: &HfClient, query: &str) -> anyhow::Result<()> {
 
 // ── Display ──────────────────────────────────────────────────────────
 
+/// Ordinal used to fold `Fit` into the same "higher is better" direction as
+/// decode/prefill throughput, for Pareto comparisons.
+fn fit_rank(fit: &Fit) -> u8 {
+    match fit {
+        Fit::NoFit => 0,
+        Fit::Partial { .. } => 1,
+        Fit::Full => 2,
+    }
+}
+
+/// True iff `a` is at least as good as `b` on every objective (decode,
+/// prefill, fit, and weight — smaller is better for weight) and strictly
+/// better on at least one, i.e. `a` Pareto-dominates `b`.
+fn dominates(a: &estimate::Estimate, b: &estimate::Estimate) -> bool {
+    let a_decode = a.decode_tok_s.unwrap_or(0.0);
+    let b_decode = b.decode_tok_s.unwrap_or(0.0);
+    let a_prefill = a.prefill_tok_s.unwrap_or(0.0);
+    let b_prefill = b.prefill_tok_s.unwrap_or(0.0);
+    let a_fit = fit_rank(&a.fit);
+    let b_fit = fit_rank(&b.fit);
+
+    let at_least_as_good = a_decode >= b_decode
+        && a_prefill >= b_prefill
+        && a_fit >= b_fit
+        && a.weight_gb <= b.weight_gb;
+    let strictly_better = a_decode > b_decode
+        || a_prefill > b_prefill
+        || a_fit > b_fit
+        || a.weight_gb < b.weight_gb;
+
+    at_least_as_good && strictly_better
+}
+
+/// Keep only the non-dominated (runtime, quant, estimate) candidates, so
+/// callers can show every real speed/memory trade-off instead of one
+/// cherry-picked "best" row.
+fn pareto_front(
+    candidates: Vec<(Runtime, estimate::Quant, estimate::Estimate)>,
+) -> Vec<(Runtime, estimate::Quant, estimate::Estimate)> {
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(i, (_, _, est))| {
+            !candidates
+                .iter()
+                .enumerate()
+                .any(|(j, (_, _, other))| j != *i && dominates(other, est))
+        })
+        .map(|(_, c)| c.clone())
+        .collect()
+}
+
 fn print_model_full(model: &Model, _variants: &[Model], opts: &Cli) {
     let tag = model.pipeline_tag.as_deref().unwrap_or("unknown");
     let param = Model::param_hint(&model.id).unwrap_or_default();
@@ -1260,7 +2251,7 @@ fn print_model_full(model: &Model, _variants: &[Model], opts: &Cli) {
 
     println!(
         "{} {}  {} {}  inference: {}",
-        s_heart().apply_to("\u{2665}"),
+        s_heart().apply_to(heart()),
         s_dim().apply_to(fmt_count(model.likes)),
         s_dim().apply_to("\u{2193}"),
         s_dim().apply_to(fmt_count(model.downloads)),
@@ -1328,48 +2319,63 @@ fn print_model_full(model: &Model, _variants: &[Model], opts: &Cli) {
     } else {
         println!("{}", s_header().apply_to("serverless providers"));
 
-        let mut table = Table::new();
-        table
-            .load_preset(presets::NOTHING)
-            .set_content_arrangement(ContentArrangement::Dynamic)
-            .set_header(vec![
-                Cell::new("Provider").fg(Color::AnsiValue(248)),
-                Cell::new("Status").fg(Color::AnsiValue(248)),
-                Cell::new("In $/1M").fg(Color::AnsiValue(248)),
-                Cell::new("Out $/1M").fg(Color::AnsiValue(248)),
-                Cell::new("Tput").fg(Color::AnsiValue(248)),
-                Cell::new("Tools").fg(Color::AnsiValue(248)),
-                Cell::new("JSON").fg(Color::AnsiValue(248)),
-            ]);
+        let dash_str = dash();
+        let check = "\u{2713}";
+        let provider_rows: Vec<Vec<String>> = providers
+            .iter()
+            .map(|p| {
+                vec![
+                    p.name.clone(),
+                    format!("{}", p.readiness()),
+                    p.input_price_per_m.map(|v| format!("${:.2}", v)).unwrap_or_else(|| dash_str.to_string()),
+                    p.output_price_per_m.map(|v| format!("${:.2}", v)).unwrap_or_else(|| dash_str.to_string()),
+                    p.throughput_tps.map(|v| format!("{:.0} t/s", v)).unwrap_or_else(|| dash_str.to_string()),
+                    (if p.supports_tools == Some(true) { check } else { dash_str }).to_string(),
+                    (if p.supports_structured == Some(true) { check } else { dash_str }).to_string(),
+                ]
+            })
+            .collect();
 
-        for p in &providers {
-            let status_color = match p.readiness() {
-                Readiness::Hot => Color::AnsiValue(114),
-                Readiness::Warm => Color::AnsiValue(214),
-                Readiness::Cold => Color::AnsiValue(208),
-                Readiness::Unavailable => Color::AnsiValue(245),
-            };
-            let dash = "\u{2500}";
-            let check = "\u{2713}";
+        if is_basic() {
+            print_basic_rows(&provider_rows);
+        } else {
+            let mut table = Table::new();
+            table
+                .load_preset(presets::NOTHING)
+                .set_content_arrangement(ContentArrangement::Dynamic)
+                .set_header(vec![
+                    Cell::new("Provider").fg(tcolor(248)),
+                    Cell::new("Status").fg(tcolor(248)),
+                    Cell::new("In $/1M").fg(tcolor(248)),
+                    Cell::new("Out $/1M").fg(tcolor(248)),
+                    Cell::new("Tput").fg(tcolor(248)),
+                    Cell::new("Tools").fg(tcolor(248)),
+                    Cell::new("JSON").fg(tcolor(248)),
+                ]);
 
-            table.add_row(vec![
-                Cell::new(&p.name).fg(Color::AnsiValue(109)),
-                Cell::new(format!("{}", p.readiness())).fg(status_color),
-                Cell::new(p.input_price_per_m.map(|v| format!("${:.2}", v)).unwrap_or_else(|| dash.into())).fg(Color::AnsiValue(109)),
-                Cell::new(p.output_price_per_m.map(|v| format!("${:.2}", v)).unwrap_or_else(|| dash.into())).fg(Color::AnsiValue(109)),
-                Cell::new(p.throughput_tps.map(|v| format!("{:.0} t/s", v)).unwrap_or_else(|| dash.into()))
-                    .fg(if p.throughput_tps.unwrap_or(0.0) >= 100.0 { Color::AnsiValue(214) } else { Color::AnsiValue(248) }),
-                Cell::new(if p.supports_tools == Some(true) { check } else { dash })
-                    .fg(if p.supports_tools == Some(true) { Color::AnsiValue(114) } else { Color::AnsiValue(245) }),
-                Cell::new(if p.supports_structured == Some(true) { check } else { dash })
-                    .fg(if p.supports_structured == Some(true) { Color::AnsiValue(114) } else { Color::AnsiValue(245) }),
-            ]);
-        }
+            for (p, row) in providers.iter().zip(provider_rows.iter()) {
+                let status_color = match p.readiness() {
+                    Readiness::Hot => tcolor(114),
+                    Readiness::Warm => tcolor(214),
+                    Readiness::Cold => tcolor(208),
+                    Readiness::Unavailable => tcolor(245),
+                };
+                table.add_row(vec![
+                    Cell::new(&row[0]).fg(tcolor(109)),
+                    Cell::new(&row[1]).fg(status_color),
+                    Cell::new(&row[2]).fg(tcolor(109)),
+                    Cell::new(&row[3]).fg(tcolor(109)),
+                    Cell::new(&row[4]).fg(if p.throughput_tps.unwrap_or(0.0) >= 100.0 { tcolor(214) } else { tcolor(248) }),
+                    Cell::new(&row[5]).fg(if p.supports_tools == Some(true) { tcolor(114) } else { tcolor(245) }),
+                    Cell::new(&row[6]).fg(if p.supports_structured == Some(true) { tcolor(114) } else { tcolor(245) }),
+                ]);
+            }
 
-        println!("{table}");
+            println!("{table}");
+        }
         println!();
 
-        let dash = "\u{2500}";
+        let dash = dash();
         let nw = [model.cheapest(), model.fastest()]
             .iter()
             .filter_map(|o| o.as_ref())
@@ -1415,23 +2421,23 @@ fn print_model_full(model: &Model, _variants: &[Model], opts: &Cli) {
             }
 
             let mut rows: Vec<EstRow> = Vec::new();
+            let mut group_starts: Vec<usize> = Vec::new();
             for (key, gpu) in &gpus {
                 if !hardware::DEFAULT_DISPLAY_GPUS.contains(&key.as_str()) {
                     continue;
                 }
-                // Pick the best runtime (highest decode tok/s).
-                let mut best: Option<(Runtime, estimate::Quant, estimate::Estimate)> = None;
+                let mut candidates: Vec<(Runtime, estimate::Quant, estimate::Estimate)> = Vec::new();
                 for rt in gpu.available_runtimes() {
-                    if let Some((q, est)) = estimate::best_quant(gpu, params, rt) {
-                        let dominated = best.as_ref().map(|(_, _, b)| {
-                            est.decode_tok_s.unwrap_or(0.0) <= b.decode_tok_s.unwrap_or(0.0)
-                        }).unwrap_or(false);
-                        if !dominated {
-                            best = Some((rt, q, est));
-                        }
+                    if let Some((q, est)) = estimate::best_quant(gpu, params, rt, &estimate::ContextSpec::default()) {
+                        candidates.push((rt, q, est));
                     }
                 }
-                if let Some((rt, q, est)) = best {
+                let front = pareto_front(candidates);
+                if front.is_empty() {
+                    continue;
+                }
+                group_starts.push(rows.len());
+                for (rt, q, est) in front {
                     let rt_label = if gpu.available_runtimes().len() > 1 {
                         rt.to_string()
                     } else {
@@ -1457,30 +2463,34 @@ fn print_model_full(model: &Model, _variants: &[Model], opts: &Cli) {
                 table.load_preset(presets::NOTHING);
                 table.set_content_arrangement(ContentArrangement::Dynamic);
                 let mut header = vec![
-                    Cell::new(" GPU").fg(Color::AnsiValue(243)),
+                    Cell::new(" GPU").fg(tcolor(243)),
                 ];
                 if has_rt {
-                    header.push(Cell::new("Rt").fg(Color::AnsiValue(243)));
+                    header.push(Cell::new("Rt").fg(tcolor(243)));
                 }
                 header.extend([
-                    Cell::new("Quant").fg(Color::AnsiValue(243)),
-                    Cell::new("Weight").fg(Color::AnsiValue(243)),
-                    Cell::new("Fit").fg(Color::AnsiValue(243)),
-                    Cell::new("Decode").fg(Color::AnsiValue(243)),
-                    Cell::new("Prefill").fg(Color::AnsiValue(243)),
+                    Cell::new("Quant").fg(tcolor(243)),
+                    Cell::new("Weight").fg(tcolor(243)),
+                    Cell::new("Fit").fg(tcolor(243)),
+                    Cell::new("Decode").fg(tcolor(243)),
+                    Cell::new("Prefill").fg(tcolor(243)),
                 ]);
                 table.set_header(header);
 
-                for est in &rows {
+                for (i, est) in rows.iter().enumerate() {
                     let fit_str = match &est.fit {
                         Fit::Full => "fits".to_string(),
+                        Fit::Partial { gpu_layers, cpu_layers } => {
+                            format!("partial ({gpu_layers}/{})", gpu_layers + cpu_layers)
+                        }
                         Fit::NoFit => "no fit".to_string(),
                     };
                     let fit_color = match &est.fit {
-                        Fit::Full => Color::AnsiValue(114),
-                            Fit::NoFit => Color::AnsiValue(245),
+                        Fit::Full => tcolor(114),
+                        Fit::Partial { .. } => tcolor(179),
+                        Fit::NoFit => tcolor(245),
                     };
-                    let dash = "\u{2500}";
+                    let dash = dash();
                     let fmt_toks = |v: Option<f64>| -> String {
                         match v {
                             Some(t) if t >= 1000.0 => format!("{:.1}k t/s", t / 1000.0),
@@ -1489,24 +2499,28 @@ fn print_model_full(model: &Model, _variants: &[Model], opts: &Cli) {
                         }
                     };
                     let decode_color = match est.decode_tok_s {
-                        Some(t) if t >= 30.0 => Color::AnsiValue(114),
-                        Some(t) if t >= 10.0 => Color::AnsiValue(214),
-                        Some(_) => Color::AnsiValue(208),
-                        None => Color::AnsiValue(245),
+                        Some(t) if t >= 30.0 => tcolor(114),
+                        Some(t) if t >= 10.0 => tcolor(214),
+                        Some(_) => tcolor(208),
+                        None => tcolor(245),
                     };
 
+                    // Blank the GPU cell on every row but the first in its
+                    // Pareto-front group, so the table reads as one block
+                    // per GPU instead of repeating the name.
+                    let gpu_cell = if group_starts.contains(&i) { est.gpu_name.as_str() } else { "" };
                     let mut row = vec![
-                        Cell::new(&est.gpu_name).fg(Color::AnsiValue(109)),
+                        Cell::new(gpu_cell).fg(tcolor(109)),
                     ];
                     if has_rt {
-                        row.push(Cell::new(&est.rt_label).fg(Color::AnsiValue(146)));
+                        row.push(Cell::new(&est.rt_label).fg(tcolor(146)));
                     }
                     row.extend([
-                        Cell::new(est.quant.label()).fg(Color::AnsiValue(248)),
-                        Cell::new(format!("{:.0} GB", est.weight_gb)).fg(Color::AnsiValue(248)),
+                        Cell::new(est.quant.label()).fg(tcolor(248)),
+                        Cell::new(format!("{:.0} GB", est.weight_gb)).fg(tcolor(248)),
                         Cell::new(&fit_str).fg(fit_color),
                         Cell::new(fmt_toks(est.decode_tok_s)).fg(decode_color),
-                        Cell::new(fmt_toks(est.prefill_tok_s)).fg(Color::AnsiValue(248)),
+                        Cell::new(fmt_toks(est.prefill_tok_s)).fg(tcolor(248)),
                     ]);
                     table.add_row(row);
                 }
@@ -1563,7 +2577,7 @@ fn print_search_results(query: &str, models: &[Model]) {
 
 // ── Interactive tree browser ─────────────────────────────────────────
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 enum NK {
     Model(String),                       // model ID
     Prov(String, String),                // (model_id, provider_name)
@@ -1578,6 +2592,10 @@ struct TreeNode {
     kind: NK,
     code: bool,
     disabled: bool,
+    /// Pre-rendered, 24-bit-ANSI-escaped version of `label` for code lines
+    /// (see `highlight_code`). `label` itself always stays plain so clipboard
+    /// copy (`Key::Char('c')`/`Enter`) never picks up escape codes.
+    highlighted: Option<String>,
 }
 
 impl TreeNode {
@@ -1622,10 +2640,93 @@ fn lang_name(l: Lang) -> &'static str {
         Lang::Python => "python",
         Lang::Curl => "curl",
         Lang::Javascript => "javascript",
+        Lang::Typescript => "typescript",
+        Lang::Go => "go",
+        Lang::OpenAIPython => "openai (python)",
+        Lang::OpenAIJavascript => "openai (js)",
+    }
+}
+
+const LANGS: [Lang; 7] = [
+    Lang::Python,
+    Lang::Curl,
+    Lang::Javascript,
+    Lang::Typescript,
+    Lang::Go,
+    Lang::OpenAIPython,
+    Lang::OpenAIJavascript,
+];
+
+/// Memoizes syntax-highlighted code blocks by `(model_id, provider_name,
+/// lang)` so `add_langs` doesn't re-run syntect on every redraw of an
+/// already-expanded snippet.
+type CodeCache = std::collections::HashMap<(String, String, Lang), Vec<String>>;
+
+/// Lazily-loaded syntax definitions, shared for the process lifetime.
+fn syntax_set() -> &'static SyntaxSet {
+    static SET: std::sync::OnceLock<SyntaxSet> = std::sync::OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Lazily-loaded color themes, shared for the process lifetime.
+fn theme_set() -> &'static ThemeSet {
+    static SET: std::sync::OnceLock<ThemeSet> = std::sync::OnceLock::new();
+    SET.get_or_init(ThemeSet::load_defaults)
+}
+
+/// Maps a snippet `Lang` to the syntax-set token syntect indexes its
+/// bundled definitions by.
+fn lang_syntax_token(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Python | Lang::OpenAIPython => "py",
+        Lang::Javascript | Lang::OpenAIJavascript => "js",
+        Lang::Typescript => "ts",
+        Lang::Go => "go",
+        Lang::Curl => "sh",
     }
 }
 
-const LANGS: [Lang; 3] = [Lang::Python, Lang::Curl, Lang::Javascript];
+/// Colorize `code` as `lang`, one 24-bit-ANSI-escaped, reset-terminated
+/// string per source line (same line count/order as `code.lines()`).
+fn highlight_code(lang: Lang, code: &str) -> Vec<String> {
+    let ss = syntax_set();
+    let syntax = ss
+        .find_syntax_by_token(lang_syntax_token(lang))
+        .unwrap_or_else(|| ss.find_syntax_plain_text());
+    let theme = &theme_set().themes["base16-ocean.dark"];
+    let mut h = HighlightLines::new(syntax, theme);
+    LinesWithEndings::from(code)
+        .map(|line| match h.highlight_line(line, ss) {
+            Ok(ranges) => {
+                let mut escaped = as_24_bit_terminal_escaped(&ranges, false);
+                while escaped.ends_with('\n') || escaped.ends_with('\r') {
+                    escaped.pop();
+                }
+                escaped.push_str("\x1b[0m");
+                escaped
+            }
+            Err(_) => line.trim_end_matches(['\n', '\r']).to_string(),
+        })
+        .collect()
+}
+
+/// Highlight `code` via `highlight_code`, reusing a cached result when the
+/// same `(model_id, prov_name, lang)` triple was already highlighted.
+fn highlighted_code_lines(
+    cache: &mut CodeCache,
+    code: &str,
+    lang: Lang,
+    model_id: &str,
+    prov_name: &str,
+) -> Vec<String> {
+    let key = (model_id.to_string(), prov_name.to_string(), lang);
+    if let Some(hit) = cache.get(&key) {
+        return hit.clone();
+    }
+    let lines = highlight_code(lang, code);
+    cache.insert(key, lines.clone());
+    lines
+}
 
 fn add_langs(
     nodes: &mut Vec<TreeNode>,
@@ -1634,9 +2735,10 @@ fn add_langs(
     exp_lang: &Option<Lang>,
     pad: &str,
     model_id: &str,
+    code_cache: &mut CodeCache,
 ) {
     for (j, &lang) in LANGS.iter().enumerate() {
-        let last = j == 2;
+        let last = j == LANGS.len() - 1;
         let conn = if last { "\u{2514}\u{2500}" } else { "\u{251c}\u{2500}" };
         nodes.push(TreeNode {
             label: format!("{pad}{conn} {}", lang_name(lang)),
@@ -1645,18 +2747,22 @@ fn add_langs(
             kind: NK::Lang(model_id.to_string(), prov.name.clone(), lang),
             code: false,
             disabled: false,
+            highlighted: None,
         });
         if *exp_lang == Some(lang) {
             let code = snippet::generate(mdl, prov, lang);
+            let hl_lines = highlighted_code_lines(code_cache, &code, lang, model_id, &prov.name);
             let cont = if last { "   " } else { "\u{2502}  " };
-            for line in code.lines() {
+            let prefix = format!("{pad}{cont}\u{258e} ");
+            for (i, line) in code.lines().enumerate() {
                 nodes.push(TreeNode {
-                    label: format!("{pad}{cont}\u{258e} {line}"),
+                    label: format!("{prefix}{line}"),
                     detail: String::new(),
                     readiness: None,
                     kind: NK::Decor,
                     code: true,
                     disabled: false,
+                    highlighted: hl_lines.get(i).map(|hl| format!("{prefix}{hl}")),
                 });
             }
         }
@@ -1694,7 +2800,13 @@ fn model_summary(m: &Model) -> String {
     parts.join("  ")
 }
 
-fn build_tree(model: &Model, variants: &[Model], var_cache: &[(String, Model)], exp: &Exp) -> Vec<TreeNode> {
+fn build_tree(
+    model: &Model,
+    variants: &[Model],
+    var_cache: &[(String, Model)],
+    exp: &Exp,
+    code_cache: &mut CodeCache,
+) -> Vec<TreeNode> {
     let mut nodes = Vec::new();
 
     // Collect all models (main + variants) for consistent layout.
@@ -1730,6 +2842,7 @@ fn build_tree(model: &Model, variants: &[Model], var_cache: &[(String, Model)],
         kind: NK::Decor,
         code: false,
         disabled: false,
+        highlighted: None,
     });
 
     for m in &all_models {
@@ -1742,6 +2855,7 @@ fn build_tree(model: &Model, variants: &[Model], var_cache: &[(String, Model)],
             kind: NK::Model(m.id.clone()),
             code: false,
             disabled: pc == 0,
+            highlighted: None,
         });
 
         // If this model is expanded, show its providers.
@@ -1762,6 +2876,7 @@ fn build_tree(model: &Model, variants: &[Model], var_cache: &[(String, Model)],
                         kind: NK::Decor,
                         code: false,
                         disabled: false,
+                        highlighted: None,
                     });
                 }
                 for p in &provs {
@@ -1772,10 +2887,11 @@ fn build_tree(model: &Model, variants: &[Model], var_cache: &[(String, Model)],
                         kind: NK::Prov(m.id.clone(), p.name.clone()),
                         code: false,
                         disabled: false,
+                        highlighted: None,
                     });
                     if let Some(pe) = prov_exp {
                         if pe.name == p.name {
-                            add_langs(&mut nodes, data, p, &pe.lang, "    ", &m.id);
+                            add_langs(&mut nodes, data, p, &pe.lang, "    ", &m.id, code_cache);
                         }
                     }
                 }
@@ -1804,6 +2920,88 @@ fn readiness_style(r: Readiness) -> Style {
     }
 }
 
+/// Hex fill matching `readiness_style`'s 256-color palette, for the DOT
+/// export (Graphviz doesn't speak terminal color codes).
+fn readiness_dot_color(r: Readiness) -> &'static str {
+    match r {
+        Readiness::Hot => "#87d75f",
+        Readiness::Warm => "#ffd75f",
+        Readiness::Cold => "#ff8700",
+        Readiness::Unavailable => "#8a8a8a",
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Assign `key` a stable incremental id the first time it's seen, reusing
+/// the same id on every later lookup (so `model -> provider` edges added
+/// after the provider node already exists still line up).
+fn dot_node_id(ids: &mut std::collections::HashMap<NK, usize>, key: NK) -> usize {
+    let next = ids.len();
+    *ids.entry(key).or_insert(next)
+}
+
+/// Render the full model → provider → language tree (main model plus
+/// variants) as a Graphviz DOT document, for `hf-providers <model> --format
+/// dot | dot -Tsvg`. Unlike `build_tree`, this walks every node rather than
+/// just the TUI's currently-expanded subset, since there's no interactive
+/// state to collapse against.
+fn model_dot(model: &Model, variants: &[Model]) -> String {
+    let all_models: Vec<&Model> = std::iter::once(model).chain(variants.iter()).collect();
+
+    let mut ids: std::collections::HashMap<NK, usize> = std::collections::HashMap::new();
+    // (id, label, fill color) in first-seen order, emitted in a second pass.
+    let mut labels: Vec<(usize, String, Option<&'static str>)> = Vec::new();
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+
+    for m in &all_models {
+        let model_id = dot_node_id(&mut ids, NK::Model(m.id.clone()));
+        labels.push((
+            model_id,
+            format!("{}\\n{}", dot_escape(&m.id), dot_escape(&model_summary(m))),
+            None,
+        ));
+
+        for p in sorted_provs(&m.providers) {
+            let prov_id = dot_node_id(&mut ids, NK::Prov(m.id.clone(), p.name.clone()));
+            labels.push((
+                prov_id,
+                format!("{}\\n{}", dot_escape(&p.name), dot_escape(prov_detail(p).trim())),
+                Some(readiness_dot_color(p.readiness())),
+            ));
+            edges.push((model_id, prov_id));
+
+            for &lang in &LANGS {
+                let lang_id = dot_node_id(&mut ids, NK::Lang(m.id.clone(), p.name.clone(), lang));
+                labels.push((lang_id, dot_escape(lang_name(lang)), None));
+                edges.push((prov_id, lang_id));
+            }
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str("digraph providers {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, fontname=\"monospace\", style=filled, fillcolor=white];\n\n");
+
+    for (id, label, fill) in &labels {
+        match fill {
+            Some(color) => out.push_str(&format!(
+                "  n{id} [id={id}] [label=\"{label}\"] [fillcolor=\"{color}\"];\n"
+            )),
+            None => out.push_str(&format!("  n{id} [id={id}] [label=\"{label}\"];\n")),
+        }
+    }
+    out.push('\n');
+    for (a, b) in &edges {
+        out.push_str(&format!("  n{a} -> n{b};\n"));
+    }
+    out.push_str("}\n");
+    out
+}
+
 fn render_tree(nodes: &[TreeNode], cursor: usize, sel: &[usize]) -> Vec<String> {
     let active = sel.get(cursor).copied().unwrap_or(usize::MAX);
     let dim = Style::new().color256(242);
@@ -1816,7 +3014,10 @@ fn render_tree(nodes: &[TreeNode], cursor: usize, sel: &[usize]) -> Vec<String>
                 let text = format!("{pfx}{}{}", n.label, n.detail);
                 format!("{}", dim.apply_to(&text))
             } else if n.code {
-                format!("{}", Style::new().color256(246).apply_to(format!("{pfx}{}", n.label)))
+                match &n.highlighted {
+                    Some(hl) if !is_plain() => format!("{pfx}{hl}"),
+                    _ => format!("{}", Style::new().color256(246).apply_to(format!("{pfx}{}", n.label))),
+                }
             } else if n.detail.is_empty() {
                 let text = format!("{pfx}{}", n.label);
                 let sty = if i == active { node_style(&n.kind).bold() } else { node_style(&n.kind) };
@@ -1847,6 +3048,93 @@ fn find_sel(nodes: &[TreeNode], sel: &[usize], target: &NK) -> Option<usize> {
     sel.iter().position(|&ni| &nodes[ni].kind == target)
 }
 
+/// Kick off a background fetch for every variant whose provider data isn't
+/// already cached and isn't already in flight, reporting results back over
+/// `tx`. Dedupes via `in_flight` so drilling into a variant that's already
+/// being prefetched doesn't start a second request.
+fn spawn_prefetch(
+    client: &HfClient,
+    variants: &[Model],
+    var_cache: &[(String, Model)],
+    in_flight: &mut std::collections::HashSet<String>,
+    tx: &tokio::sync::mpsc::UnboundedSender<(String, Option<Model>)>,
+) {
+    for v in variants {
+        if !v.providers.is_empty() || var_cache.iter().any(|(k, _)| k == &v.id) {
+            continue;
+        }
+        if !in_flight.insert(v.id.clone()) {
+            continue;
+        }
+        let client = client.clone();
+        let id = v.id.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let short = id.rsplit('/').next().unwrap_or(&id).to_string();
+            let found = client
+                .search_models(&short, 5)
+                .await
+                .ok()
+                .and_then(|results| results.iter().filter_map(parse_model).find(|m| m.id == id));
+            let _ = tx.send((id, found));
+        });
+    }
+}
+
+/// Run the clipboard helper `cmd` with `args`, piping `text` into its stdin.
+/// Returns `true` only if it spawns, accepts the text and exits
+/// successfully.
+fn copy_via_command(cmd: &str, args: &[&str], text: &str) -> bool {
+    let Ok(mut child) = std::process::Command::new(cmd)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(ref mut stdin) = child.stdin {
+        if stdin.write_all(text.as_bytes()).is_err() {
+            return false;
+        }
+    }
+    child.wait().map(|s| s.success()).unwrap_or(false)
+}
+
+/// Emit an OSC 52 "set clipboard" escape sequence directly to the
+/// terminal. This is the only option that survives an SSH hop, since it
+/// asks the *local* terminal emulator (not the remote host) to own the
+/// clipboard — supporting emulators include iTerm2, kitty, WezTerm, and
+/// Windows Terminal.
+fn copy_via_osc52(text: &str) -> bool {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    eprint!("\x1b]52;c;{encoded}\x07");
+    std::io::stderr().flush().is_ok()
+}
+
+/// Copy `text` to the system clipboard, probing local clipboard tools
+/// before falling back to OSC 52 (for remote/SSH sessions, or when none of
+/// the local tools are installed). Returns whether any method succeeded.
+fn copy_to_clipboard(text: &str) -> bool {
+    let over_ssh = std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+    if !over_ssh {
+        let attempts: &[(&str, &[&str])] = &[
+            ("pbcopy", &[]),
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+            ("clip.exe", &[]),
+        ];
+        for &(cmd, args) in attempts {
+            if copy_via_command(cmd, args, text) {
+                return true;
+            }
+        }
+    }
+    copy_via_osc52(text)
+}
+
 async fn interactive_picker(
     client: &HfClient,
     model: &Model,
@@ -1858,10 +3146,25 @@ async fn interactive_picker(
     let mut cursor: usize = 0;
     let mut drawn: usize = 0;
     let mut var_cache: Vec<(String, Model)> = Vec::new();
+    let mut code_cache: CodeCache = CodeCache::new();
     let mut status: Option<String> = None;
+    // Scroll offset into the body (everything but the pinned key-hint
+    // header), so long provider lists and expanded code snippets don't
+    // overflow the terminal.
+    let mut scroll_offset: usize = 0;
+
+    // Background prefetch: fetch every variant's provider data as soon as
+    // it's visible, so drilling into it later is instant instead of
+    // blocking on `client.search_models`.
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(String, Option<Model>)>();
+    let mut in_flight: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let pulse = ['\u{2731}', '\u{2726}', '\u{00b7}', '\u{2726}'];
+    let mut spin_frame: usize = 0;
+    let mut pending_key: Option<tokio::task::JoinHandle<std::io::Result<Key>>> = None;
+    spawn_prefetch(client, variants, &var_cache, &mut in_flight, &tx);
 
-    loop {
-        let nodes = build_tree(model, variants, &var_cache, &exp);
+    'draw: loop {
+        let nodes = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
         let sel = sel_indices(&nodes);
         if sel.is_empty() {
             break;
@@ -1869,25 +3172,93 @@ async fn interactive_picker(
         cursor = cursor.min(sel.len() - 1);
 
         let lines = render_tree(&nodes, cursor, &sel);
+        // nodes[0] (the key-hint header) is pinned above the scrollable body.
+        let body = &lines[1..];
+        let active_idx = sel.get(cursor).copied().unwrap_or(0);
+        let active_in_body = active_idx.saturating_sub(1);
+
+        let (term_rows, _) = term.size();
+        // Reserve one row for the pinned header and one for the transient
+        // status line.
+        let view_rows = (term_rows as usize).saturating_sub(2).max(3);
+
+        if active_in_body < scroll_offset {
+            scroll_offset = active_in_body;
+        }
+        if active_in_body >= scroll_offset + view_rows {
+            scroll_offset = active_in_body + 1 - view_rows;
+        }
+        scroll_offset = scroll_offset.min(body.len().saturating_sub(view_rows));
+
+        let end = (scroll_offset + view_rows).min(body.len());
+        let dim = Style::new().color256(242);
+
+        let mut display: Vec<String> = Vec::with_capacity(view_rows + 3);
+        display.push(lines[0].clone());
+        if scroll_offset > 0 {
+            display.push(format!("{}", dim.apply_to(format!("  \u{25b2} {} more", scroll_offset))));
+        }
+        display.extend(body[scroll_offset..end].iter().cloned());
+        if end < body.len() {
+            display.push(format!("{}", dim.apply_to(format!("  \u{25bc} {} more", body.len() - end))));
+        }
+
+        let loading_line = (!in_flight.is_empty()).then(|| {
+            format!(
+                "  {}",
+                s_dim().apply_to(format!(
+                    "{} loading {} model{}\u{2026}",
+                    pulse[spin_frame % pulse.len()],
+                    in_flight.len(),
+                    if in_flight.len() == 1 { "" } else { "s" }
+                ))
+            )
+        });
+
         if drawn > 0 {
             term.clear_last_lines(drawn)?;
         }
-        for line in &lines {
+        for line in &display {
             term.write_line(line)?;
         }
         if let Some(ref msg) = status {
             term.write_line("")?;
             term.write_line(msg)?;
-            drawn = lines.len() + 2;
+            drawn = display.len() + 2;
             status = None;
+        } else if let Some(ref line) = loading_line {
+            term.write_line(line)?;
+            drawn = display.len() + 1;
         } else {
-            drawn = lines.len();
+            drawn = display.len();
         }
 
-        let key = {
+        if pending_key.is_none() {
             let t = Term::stderr();
-            tokio::task::spawn_blocking(move || t.read_key()).await?
-        }?;
+            pending_key = Some(tokio::task::spawn_blocking(move || t.read_key()));
+        }
+
+        // Wait for either a keypress or a prefetch completion; a completion
+        // just updates `var_cache`/`in_flight` and loops back to redraw
+        // (spinner, newly-instant drill-in) without consuming a keystroke.
+        let key = loop {
+            tokio::select! {
+                res = pending_key.as_mut().unwrap() => {
+                    pending_key = None;
+                    break res??;
+                }
+                Some((id, found)) = rx.recv() => {
+                    in_flight.remove(&id);
+                    if let Some(m) = found {
+                        if !var_cache.iter().any(|(k, _)| k == &id) {
+                            var_cache.push((id, m));
+                        }
+                    }
+                    spin_frame = spin_frame.wrapping_add(1);
+                    continue 'draw;
+                }
+            }
+        };
 
         match key {
             Key::ArrowUp | Key::Char('k') => cursor = cursor.saturating_sub(1),
@@ -1905,7 +3276,7 @@ async fn interactive_picker(
                         let already = matches!(&exp, Exp::Open { model_id, .. } if model_id == id);
                         if already {
                             // Already open — move into first child provider.
-                            let nn = build_tree(model, variants, &var_cache, &exp);
+                            let nn = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
                             let ns = sel_indices(&nn);
                             let pos = ns.iter().position(|&ni| {
                                 matches!(&nn[ni].kind, NK::Prov(mid, _) if mid == id)
@@ -1938,7 +3309,7 @@ async fn interactive_picker(
                                 }
                             }
                             exp = Exp::Open { model_id: id.clone(), prov: None };
-                            let nn = build_tree(model, variants, &var_cache, &exp);
+                            let nn = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
                             let ns = sel_indices(&nn);
                             if let Some(p) = find_sel(&nn, &ns, &kind) {
                                 cursor = p;
@@ -1952,14 +3323,14 @@ async fn interactive_picker(
                                 if already {
                                     // Move into first lang child.
                                     let t = NK::Lang(mid.clone(), pname.clone(), Lang::Python);
-                                    let nn = build_tree(model, variants, &var_cache, &exp);
+                                    let nn = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
                                     let ns = sel_indices(&nn);
                                     if let Some(p) = find_sel(&nn, &ns, &t) {
                                         cursor = p;
                                     }
                                 } else {
                                     *pe = Some(ProvExp { name: pname.clone(), lang: None });
-                                    let nn = build_tree(model, variants, &var_cache, &exp);
+                                    let nn = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
                                     let ns = sel_indices(&nn);
                                     if let Some(p) = find_sel(&nn, &ns, &kind) {
                                         cursor = p;
@@ -1974,7 +3345,7 @@ async fn interactive_picker(
                                 pe.lang = Some(lang);
                             }
                         }
-                        let nn = build_tree(model, variants, &var_cache, &exp);
+                        let nn = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
                         let ns = sel_indices(&nn);
                         if let Some(p) = find_sel(&nn, &ns, &kind) {
                             cursor = p;
@@ -2032,7 +3403,7 @@ async fn interactive_picker(
                 }
 
                 if need_rebuild {
-                    let nn = build_tree(model, variants, &var_cache, &exp);
+                    let nn = build_tree(model, variants, &var_cache, &exp, &mut code_cache);
                     let ns = sel_indices(&nn);
                     if let Some(p) = find_sel(&nn, &ns, &kind) {
                         cursor = p;
@@ -2051,26 +3422,26 @@ async fn interactive_picker(
                     .collect::<Vec<_>>()
                     .join("\n");
                 if !code.is_empty() {
-                    if let Ok(mut child) = std::process::Command::new("pbcopy")
-                        .stdin(std::process::Stdio::piped())
-                        .spawn()
-                    {
-                        if let Some(ref mut stdin) = child.stdin {
-                            let _ = stdin.write_all(code.as_bytes());
-                        }
-                        let _ = child.wait();
-                        let what = match &exp {
-                            Exp::Open { model_id, prov: Some(pe) } if pe.lang.is_some() =>
-                                format!("{}:{} ({})", model_id, pe.name, lang_name(pe.lang.unwrap())),
-                            _ => "code".to_string(),
-                        };
-                        status = Some(format!(
+                    let what = match &exp {
+                        Exp::Open { model_id, prov: Some(pe) } if pe.lang.is_some() =>
+                            format!("{}:{} ({})", model_id, pe.name, lang_name(pe.lang.unwrap())),
+                        _ => "code".to_string(),
+                    };
+                    status = Some(if copy_to_clipboard(&code) {
+                        format!(
                             "  {}",
                             Style::new().color256(114).apply_to(
                                 format!("\u{2500}\u{2500} \u{2713} copied {} \u{2500}\u{2500}", what)
                             )
-                        ));
-                    }
+                        )
+                    } else {
+                        format!(
+                            "  {}",
+                            s_err().apply_to(
+                                format!("\u{2500}\u{2500} \u{2717} could not copy {} \u{2500}\u{2500}", what)
+                            )
+                        )
+                    });
                 }
             }
 
@@ -2119,31 +3490,58 @@ fn extract_core_name(model_id: &str) -> String {
 
 // ── Sync ─────────────────────────────────────────────────────────────
 
-async fn cmd_sync() -> anyhow::Result<()> {
-    let term = Term::stderr();
-    term.write_line(&format!("{}", s_dim().apply_to("downloading latest data...")))?;
+async fn cmd_sync(config: &AppConfig, force: bool, ttl_hours: u64) -> anyhow::Result<()> {
+    use hf_providers_core::sync::SyncOutcome;
 
-    let result = hf_providers_core::sync::sync_data().await?;
+    let term = Term::stderr();
+    let ttl = std::time::Duration::from_secs(ttl_hours * 60 * 60);
 
+    term.write_line(&format!("{}", s_dim().apply_to("checking cache...")))?;
+    let outcome =
+        hf_providers_core::sync::sync_if_stale(ttl, force, config.sync.base_url.as_deref()).await?;
     term.clear_last_lines(1)?;
-    println!();
-    println!(
-        "  {}",
-        s_hot().apply_to("synced")
-    );
-    println!(
-        "  {}",
-        s_dim().apply_to(format!(
-            "hardware.toml: {} GPUs   cloud.toml: {} offerings",
-            result.hardware_count, result.cloud_count
-        ))
-    );
-    if let Some(dir) = hf_providers_core::cache::cache_dir() {
-        println!(
-            "  {}",
-            s_hint().apply_to(format!("cached in {}", dir.display()))
-        );
+
+    match outcome {
+        SyncOutcome::Fresh { age } => {
+            println!();
+            println!(
+                "  {}",
+                s_dim().apply_to(format!("cache is fresh (synced {}h ago)", age.as_secs() / 3600))
+            );
+            println!(
+                "  {}",
+                s_hint().apply_to("use --force to re-download anyway")
+            );
+            println!();
+        }
+        SyncOutcome::Synced(result) => {
+            use hf_providers_core::sync::{FileStatus, FileSync};
+            println!();
+            println!(
+                "  {}",
+                s_hot().apply_to("synced")
+            );
+            let describe = |file: &FileSync, noun: &str| match &file.status {
+                FileStatus::Updated(count) => format!("{count} {noun} (updated)"),
+                FileStatus::Unchanged => "unchanged".to_string(),
+                FileStatus::Offline => format!("offline, kept existing data (after {} attempts)", file.attempts),
+            };
+            println!(
+                "  {}",
+                s_dim().apply_to(format!(
+                    "hardware.toml: {}   cloud.toml: {}",
+                    describe(&result.hardware, "GPUs"),
+                    describe(&result.cloud, "offerings"),
+                ))
+            );
+            if let Some(dir) = hf_providers_core::cache::cache_dir() {
+                println!(
+                    "  {}",
+                    s_hint().apply_to(format!("cached in {}", dir.display()))
+                );
+            }
+            println!();
+        }
     }
-    println!();
     Ok(())
 }
@@ -1,4 +1,11 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{ETAG, IF_NONE_MATCH, LINK};
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::error::{HfpError, Result};
@@ -7,9 +14,87 @@ use crate::provider::{ProviderInfo, ProviderStatus};
 
 const HF_API: &str = "https://huggingface.co/api";
 
+/// On-disk response cache configuration for [`HfClient`].
+#[derive(Clone)]
+struct CacheConfig {
+    dir: PathBuf,
+    ttl: Duration,
+    /// Skip the cache entirely (still writes fresh responses back to it).
+    bypass: bool,
+}
+
+/// A cached response: its body plus the `ETag` needed to revalidate it.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    etag: Option<String>,
+    fetched_at: u64,
+    body: Value,
+}
+
+/// Outcome of a cache-aware GET, before endpoint-specific error mapping.
+enum Fetched {
+    Ok(Value),
+    NotFound,
+    Err(u16, String),
+}
+
+/// Retry policy for transient failures (429/500/502/503, network/timeout
+/// errors). Non-retryable statuses like 404/401 short-circuit immediately.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 3, base_delay: Duration::from_millis(500) }
+    }
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 502 | 503)
+}
+
+/// Exponential backoff with jitter: `base_delay * 2^(attempt - 1)`, plus up
+/// to 25% jitter to avoid synchronized retries across clients.
+pub(crate) fn backoff_delay(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let exp = 2u32.saturating_pow(attempt.saturating_sub(1));
+    let backoff = policy.base_delay.saturating_mul(exp);
+    let jitter_cap = (backoff.as_millis() as u64 / 4).max(1);
+    backoff + Duration::from_millis(jitter_ms(jitter_cap))
+}
+
+/// Cheap, non-cryptographic jitter source — good enough to desynchronize
+/// retries, not meant for anything security-sensitive.
+fn jitter_ms(max_ms: u64) -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    nanos as u64 % max_ms
+}
+
+/// Parse a `Retry-After` header value as whole seconds (the HTTP-date form
+/// isn't handled — HF's API only ever sends the delta-seconds form).
+fn retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    let secs: u64 = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+#[derive(Clone)]
 pub struct HfClient {
     http: Client,
     token: Option<String>,
+    cache: Option<CacheConfig>,
+    retry: RetryPolicy,
+    api_base: String,
 }
 
 impl HfClient {
@@ -20,9 +105,19 @@ impl HfClient {
                 .build()
                 .expect("failed to build HTTP client"),
             token,
+            cache: None,
+            retry: RetryPolicy::default(),
+            api_base: HF_API.to_string(),
         }
     }
 
+    /// Point at a self-hosted gateway or corporate proxy instead of the
+    /// public `huggingface.co/api` endpoint.
+    pub fn with_api_base(mut self, base: impl Into<String>) -> Self {
+        self.api_base = base.into();
+        self
+    }
+
     /// Try to find token from env or `~/.cache/huggingface/token`.
     pub fn with_auto_token() -> Self {
         let token = std::env::var("HF_TOKEN")
@@ -37,73 +132,185 @@ impl HfClient {
         Self::new(token)
     }
 
+    /// Enable the on-disk ETag-aware response cache under `dir`. Entries
+    /// younger than `ttl` are served without contacting the server at all;
+    /// older entries get revalidated via `If-None-Match` and only
+    /// re-downloaded on a non-304 response.
+    pub fn with_cache(mut self, dir: PathBuf, ttl: Duration) -> Self {
+        self.cache = Some(CacheConfig { dir, ttl, bypass: false });
+        self
+    }
+
+    /// Skip the cache for subsequent requests (e.g. an interactive "refresh"
+    /// action), while still writing fresh responses back to it.
+    pub fn bypass_cache(mut self) -> Self {
+        if let Some(cache) = &mut self.cache {
+            cache.bypass = true;
+        }
+        self
+    }
+
+    /// Override the default retry policy for transient failures.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
     fn auth_header(&self) -> Option<String> {
         self.token.as_ref().map(|t| format!("Bearer {t}"))
     }
 
+    fn cache_key(url: &str) -> String {
+        let mut h = DefaultHasher::new();
+        url.hash(&mut h);
+        format!("{:016x}.json", h.finish())
+    }
+
+    fn read_cache_entry(cfg: &CacheConfig, url: &str) -> Option<CacheEntry> {
+        let data = std::fs::read_to_string(cfg.dir.join(Self::cache_key(url))).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    fn write_cache_entry(cfg: &CacheConfig, url: &str, etag: Option<&str>, body: &Value) {
+        let entry = CacheEntry {
+            etag: etag.map(String::from),
+            fetched_at: now_unix(),
+            body: body.clone(),
+        };
+        if let Ok(json) = serde_json::to_string(&entry) {
+            let _ = std::fs::create_dir_all(&cfg.dir);
+            let _ = std::fs::write(cfg.dir.join(Self::cache_key(url)), json);
+        }
+    }
+
+    fn is_fresh(cfg: &CacheConfig, entry: &CacheEntry) -> bool {
+        now_unix().saturating_sub(entry.fetched_at) < cfg.ttl.as_secs()
+    }
+
+    /// Send a GET with the configured retry policy: retries on 429/500/502/503
+    /// and network/timeout errors with exponential backoff and jitter, honoring
+    /// a `Retry-After` header verbatim when present. Other statuses (including
+    /// 404/401) return immediately on the first attempt.
+    async fn send_with_retry(&self, url: &str, etag: Option<&str>) -> Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let mut req = self.http.get(url);
+            if let Some(auth) = self.auth_header() {
+                req = req.header("Authorization", auth);
+            }
+            if let Some(etag) = etag {
+                req = req.header(IF_NONE_MATCH, etag.to_string());
+            }
+
+            match req.send().await {
+                Ok(resp) => {
+                    let status = resp.status().as_u16();
+                    if attempt >= self.retry.max_attempts || !is_retryable_status(status) {
+                        return Ok(resp);
+                    }
+                    let delay = retry_after(&resp).unwrap_or_else(|| backoff_delay(&self.retry, attempt));
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    if attempt >= self.retry.max_attempts {
+                        return Err(HfpError::Http(e));
+                    }
+                    tokio::time::sleep(backoff_delay(&self.retry, attempt)).await;
+                }
+            }
+        }
+    }
+
+    /// GET `url`, transparently using the on-disk cache when configured:
+    /// serves unexpired entries directly, revalidates stale ones with
+    /// `If-None-Match`, and refreshes the cache on a `200`.
+    async fn get_cached(&self, url: &str) -> Result<Fetched> {
+        let cfg = self.cache.as_ref().filter(|c| !c.bypass);
+        let cached = cfg.and_then(|c| Self::read_cache_entry(c, url));
+
+        if let (Some(cfg), Some(entry)) = (cfg, &cached) {
+            if Self::is_fresh(cfg, entry) {
+                return Ok(Fetched::Ok(entry.body.clone()));
+            }
+        }
+
+        let etag = cached.as_ref().and_then(|e| e.etag.as_deref());
+        let resp = self.send_with_retry(url, etag).await?;
+        let status = resp.status().as_u16();
+
+        if status == 304 {
+            if let Some(entry) = cached {
+                return Ok(Fetched::Ok(entry.body));
+            }
+        }
+        if status == 404 {
+            return Ok(Fetched::NotFound);
+        }
+        if !resp.status().is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Ok(Fetched::Err(status, body));
+        }
+
+        let etag = resp
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let body: Value = resp.json().await?;
+        if let Some(cfg) = self.cache.as_ref() {
+            Self::write_cache_entry(cfg, url, etag.as_deref(), &body);
+        }
+        Ok(Fetched::Ok(body))
+    }
+
+    fn value_or_err(fetched: Fetched) -> Result<Value> {
+        match fetched {
+            Fetched::Ok(v) => Ok(v),
+            Fetched::NotFound => Err(HfpError::Api { status: 404, body: String::new() }),
+            Fetched::Err(status, body) => Err(HfpError::Api { status, body }),
+        }
+    }
+
     /// Get full model info with provider mapping.
     pub async fn model_info(&self, model_id: &str) -> Result<Value> {
+        let api = &self.api_base;
         let url = format!(
-            "{HF_API}/models/{model_id}?\
+            "{api}/models/{model_id}?\
              expand[]=inferenceProviderMapping&expand[]=inference\
              &expand[]=tags&expand[]=cardData&expand[]=library_name\
              &expand[]=likes&expand[]=downloads&expand[]=pipeline_tag"
         );
-        let mut req = self.http.get(&url);
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-        let resp = req.send().await?;
-        let status = resp.status().as_u16();
-        if status == 404 {
-            return Err(HfpError::ModelNotFound(model_id.to_string()));
+        match self.get_cached(&url).await? {
+            Fetched::Ok(v) => Ok(v),
+            Fetched::NotFound => Err(HfpError::ModelNotFound(model_id.to_string())),
+            Fetched::Err(status, body) => Err(HfpError::Api { status, body }),
         }
-        if !resp.status().is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            return Err(HfpError::Api { status, body });
-        }
-        Ok(resp.json().await?)
     }
 
     /// Search models by query string.
     pub async fn search_models(&self, query: &str, limit: u32) -> Result<Vec<Value>> {
+        let api = &self.api_base;
         let url = format!(
-            "{HF_API}/models?search={}&limit={limit}\
+            "{api}/models?search={}&limit={limit}\
              &expand[]=inferenceProviderMapping&sort=likes&direction=-1",
             urlencoding::encode(query),
         );
-        let mut req = self.http.get(&url);
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-        let resp = req.send().await?;
-        if !resp.status().is_success() {
-            let status = resp.status().as_u16();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(HfpError::Api { status, body });
-        }
-        Ok(resp.json().await?)
+        let body = Self::value_or_err(self.get_cached(&url).await?)?;
+        Ok(serde_json::from_value(body)?)
     }
 
     /// Fetch top models by trending score (with provider data).
     pub async fn trending_models(&self, limit: u32) -> Result<Vec<Value>> {
+        let api = &self.api_base;
         let url = format!(
-            "{HF_API}/models?sort=trendingScore&direction=-1&limit={limit}\
+            "{api}/models?sort=trendingScore&direction=-1&limit={limit}\
              &expand[]=inferenceProviderMapping&expand[]=inference\
              &expand[]=likes&expand[]=downloads&expand[]=pipeline_tag\
              &expand[]=library_name&expand[]=tags"
         );
-        let mut req = self.http.get(&url);
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-        let resp = req.send().await?;
-        if !resp.status().is_success() {
-            let status = resp.status().as_u16();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(HfpError::Api { status, body });
-        }
-        Ok(resp.json().await?)
+        let body = Self::value_or_err(self.get_cached(&url).await?)?;
+        Ok(serde_json::from_value(body)?)
     }
 
     /// List models served by a specific provider.
@@ -113,30 +320,155 @@ impl HfClient {
         task: Option<&str>,
         limit: u32,
     ) -> Result<Vec<Value>> {
+        let api = &self.api_base;
         let mut url = format!(
-            "{HF_API}/models?inference_provider={provider}\
+            "{api}/models?inference_provider={provider}\
              &limit={limit}&sort=likes&direction=-1"
         );
         if let Some(t) = task {
             url.push_str(&format!("&pipeline_tag={t}"));
         }
-        let mut req = self.http.get(&url);
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-        let resp = req.send().await?;
-        if !resp.status().is_success() {
+        let body = Self::value_or_err(self.get_cached(&url).await?)?;
+        Ok(serde_json::from_value(body)?)
+    }
+
+    /// Follow the `Link: rel="next"` cursor across pages of `first_url`,
+    /// parsing each page's entries into [`Model`]s and accumulating them
+    /// until the endpoint is exhausted or `max` models have been collected.
+    /// Bypasses the on-disk cache: pagination is for bulk enumeration, not
+    /// the cached "latest N" single-page lookups.
+    async fn fetch_all(&self, first_url: String, max: usize) -> Result<Vec<Model>> {
+        let mut out = Vec::new();
+        let mut next_url = Some(first_url);
+        while let Some(url) = next_url.take() {
+            if out.len() >= max {
+                break;
+            }
+            let resp = self.send_with_retry(&url, None).await?;
             let status = resp.status().as_u16();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(HfpError::Api { status, body });
+            if !resp.status().is_success() {
+                let body = resp.text().await.unwrap_or_default();
+                return Err(HfpError::Api { status, body });
+            }
+            next_url = parse_next_link(resp.headers());
+            let values: Vec<Value> = resp.json().await?;
+            for v in values {
+                if out.len() >= max {
+                    break;
+                }
+                if let Some(model) = parse_model(&v) {
+                    out.push(model);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Enumerate all models matching `query`, paginating `page_size` at a
+    /// time up to `max` results.
+    pub async fn fetch_all_search(&self, query: &str, page_size: u32, max: usize) -> Result<Vec<Model>> {
+        let api = &self.api_base;
+        let url = format!(
+            "{api}/models?search={}&limit={page_size}\
+             &expand[]=inferenceProviderMapping&sort=likes&direction=-1",
+            urlencoding::encode(query),
+        );
+        self.fetch_all(url, max).await
+    }
+
+    /// Enumerate trending models, paginating `page_size` at a time up to
+    /// `max` results.
+    pub async fn fetch_all_trending(&self, page_size: u32, max: usize) -> Result<Vec<Model>> {
+        let api = &self.api_base;
+        let url = format!(
+            "{api}/models?sort=trendingScore&direction=-1&limit={page_size}\
+             &expand[]=inferenceProviderMapping&expand[]=inference\
+             &expand[]=likes&expand[]=downloads&expand[]=pipeline_tag\
+             &expand[]=library_name&expand[]=tags"
+        );
+        self.fetch_all(url, max).await
+    }
+
+    /// Enumerate all models served by `provider`, paginating `page_size` at a
+    /// time up to `max` results.
+    pub async fn fetch_all_by_provider(
+        &self,
+        provider: &str,
+        task: Option<&str>,
+        page_size: u32,
+        max: usize,
+    ) -> Result<Vec<Model>> {
+        let api = &self.api_base;
+        let mut url = format!(
+            "{api}/models?inference_provider={provider}\
+             &limit={page_size}&sort=likes&direction=-1"
+        );
+        if let Some(t) = task {
+            url.push_str(&format!("&pipeline_tag={t}"));
         }
-        Ok(resp.json().await?)
+        self.fetch_all(url, max).await
     }
 }
 
-/// Parse raw HF API JSON into our [`Model`] type.
+/// Parse the RFC 5988 `Link` header for a `rel="next"` URL.
+fn parse_next_link(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let raw = headers.get(LINK)?.to_str().ok()?;
+    raw.split(',').find_map(|part| {
+        let mut segs = part.split(';').map(str::trim);
+        let url_part = segs.next()?;
+        if segs.any(|s| s == r#"rel="next""#) {
+            Some(url_part.trim_start_matches('<').trim_end_matches('>').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A structured account of a payload shape `parse_model` couldn't fully
+/// trust, surfaced by [`parse_model_verbose`] instead of silently dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseWarning {
+    /// The payload has no (string) `id` field, so no [`Model`] could be
+    /// built at all.
+    MissingId,
+    /// An `inferenceProviderMapping` array entry has no `provider` field and
+    /// was skipped entirely. `index` is its position in the array.
+    MissingProviderField { index: usize },
+    /// A provider's `status` didn't match a known value (or was absent);
+    /// recorded as [`ProviderStatus::Unknown`].
+    UnknownStatus { provider: String, raw: Option<String> },
+    /// A provider entry carries no price data for either direction.
+    MissingPricing { provider: String },
+}
+
+/// Parse raw HF API JSON into our [`Model`] type, discarding any warnings.
+/// Use [`parse_model_verbose`] to distinguish "model genuinely has no
+/// providers" from "the upstream payload shape changed and data was
+/// dropped."
 pub fn parse_model(data: &Value) -> Option<Model> {
-    let id = data.get("id")?.as_str()?.to_string();
+    parse_model_verbose(data).0
+}
+
+/// Like [`parse_model`], but returns every skipped or partial entry as a
+/// [`ParseWarning`] alongside the best-effort [`Model`].
+pub fn parse_model_verbose(data: &Value) -> (Option<Model>, Vec<ParseWarning>) {
+    let mut warnings = Vec::new();
+
+    let id = match data.get("id").and_then(|v| v.as_str()) {
+        Some(id) => id.to_string(),
+        None => {
+            warnings.push(ParseWarning::MissingId);
+            return (None, warnings);
+        }
+    };
+
     let pipeline_tag = data
         .get("pipeline_tag")
         .and_then(|v| v.as_str())
@@ -151,27 +483,47 @@ pub fn parse_model(data: &Value) -> Option<Model> {
         .and_then(|v| v.as_str())
         .map(String::from);
 
+    fn parse_status(raw: Option<&str>, provider: &str, warnings: &mut Vec<ParseWarning>) -> ProviderStatus {
+        match raw {
+            Some("live") => ProviderStatus::Live,
+            Some("staging") => ProviderStatus::Staging,
+            _ => {
+                warnings.push(ParseWarning::UnknownStatus {
+                    provider: provider.to_string(),
+                    raw: raw.map(String::from),
+                });
+                ProviderStatus::Unknown
+            }
+        }
+    }
+
     let mut providers = Vec::new();
     if let Some(ipm) = data.get("inferenceProviderMapping") {
         if let Some(arr) = ipm.as_array() {
             // Search endpoint: array of objects with "provider" field + full data
-            for info in arr {
+            for (index, info) in arr.iter().enumerate() {
                 let name = match info.get("provider").and_then(|v| v.as_str()) {
                     Some(n) => n.to_string(),
-                    None => continue,
+                    None => {
+                        warnings.push(ParseWarning::MissingProviderField { index });
+                        continue;
+                    }
                 };
                 let perf = info.get("performance");
                 let details = info.get("providerDetails");
                 let features = info.get("features");
                 let pricing = details.and_then(|d| d.get("pricing"));
 
+                let status = parse_status(info.get("status").and_then(|v| v.as_str()), &name, &mut warnings);
+                let input_price_per_m = pricing.and_then(|p| p.get("input")).and_then(|v| v.as_f64());
+                let output_price_per_m = pricing.and_then(|p| p.get("output")).and_then(|v| v.as_f64());
+                if input_price_per_m.is_none() && output_price_per_m.is_none() {
+                    warnings.push(ParseWarning::MissingPricing { provider: name.clone() });
+                }
+
                 providers.push(ProviderInfo {
                     name,
-                    status: match info.get("status").and_then(|v| v.as_str()) {
-                        Some("live") => ProviderStatus::Live,
-                        Some("staging") => ProviderStatus::Staging,
-                        _ => ProviderStatus::Unknown,
-                    },
+                    status,
                     task: info
                         .get("task")
                         .and_then(|v| v.as_str())
@@ -182,12 +534,8 @@ pub fn parse_model(data: &Value) -> Option<Model> {
                         .and_then(|v| v.as_str())
                         .unwrap_or("")
                         .to_string(),
-                    input_price_per_m: pricing
-                        .and_then(|p| p.get("input"))
-                        .and_then(|v| v.as_f64()),
-                    output_price_per_m: pricing
-                        .and_then(|p| p.get("output"))
-                        .and_then(|v| v.as_f64()),
+                    input_price_per_m,
+                    output_price_per_m,
                     throughput_tps: perf
                         .and_then(|p| p.get("tokensPerSecond"))
                         .and_then(|v| v.as_f64()),
@@ -209,13 +557,12 @@ pub fn parse_model(data: &Value) -> Option<Model> {
         } else if let Some(obj) = ipm.as_object() {
             // Detail endpoint: object keyed by provider name (minimal data)
             for (name, info) in obj {
+                let status = parse_status(info.get("status").and_then(|v| v.as_str()), name, &mut warnings);
+                warnings.push(ParseWarning::MissingPricing { provider: name.clone() });
+
                 providers.push(ProviderInfo {
                     name: name.clone(),
-                    status: match info.get("status").and_then(|v| v.as_str()) {
-                        Some("live") => ProviderStatus::Live,
-                        Some("staging") => ProviderStatus::Staging,
-                        _ => ProviderStatus::Unknown,
-                    },
+                    status,
                     task: info
                         .get("task")
                         .and_then(|v| v.as_str())
@@ -264,7 +611,7 @@ pub fn parse_model(data: &Value) -> Option<Model> {
                 .map(|t| t.strip_prefix("license:").unwrap().to_string())
         });
 
-    Some(Model {
+    let model = Model {
         id,
         pipeline_tag,
         likes,
@@ -275,5 +622,7 @@ pub fn parse_model(data: &Value) -> Option<Model> {
         tags,
         library_name,
         license,
-    })
+    };
+
+    (Some(model), warnings)
 }
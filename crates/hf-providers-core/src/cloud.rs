@@ -48,12 +48,16 @@ pub fn load_bundled_cloud() -> Result<Vec<(String, CloudOffering)>> {
 }
 
 /// Load cloud data: cached file if available, otherwise bundled.
+/// Falls back to bundled data if the cached file fails the checksum
+/// recorded in its sidecar metadata (corruption detected).
 #[cfg(feature = "network")]
 pub fn load_cloud_cached() -> Result<Vec<(String, CloudOffering)>> {
     if let Some(path) = crate::cache::cache_path("cloud.toml") {
         if let Ok(content) = std::fs::read_to_string(&path) {
-            if let Ok(parsed) = parse_cloud(&content) {
-                return Ok(parsed);
+            if crate::sync::verify_checksum("cloud.toml", &content) {
+                if let Ok(parsed) = parse_cloud(&content) {
+                    return Ok(parsed);
+                }
             }
         }
     }
@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "network")]
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "network")]
+use crate::error::{HfpError, Result};
+use crate::hardware::{GpuSpec, Runtime};
+
+/// User preferences and cost-model assumptions, loaded from
+/// `~/.config/hf-providers/config.toml` (or a `--config` override) with
+/// built-in defaults for anything left unset.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct AppConfig {
+    pub electricity: ElectricityConfig,
+    pub cost_model: CostModelConfig,
+    pub defaults: DefaultsConfig,
+    pub sync: SyncConfig,
+    /// Per-GPU patches (keyed the same as `hardware.toml`, e.g. `rtx_4090`),
+    /// applied on top of the bundled/cached hardware database.
+    pub gpu_overrides: BTreeMap<String, GpuOverride>,
+}
+
+/// Assumptions behind the "local GPU" electricity-cost estimate.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ElectricityConfig {
+    pub price_per_kwh: f64,
+    pub utilization: f64,
+    pub hours_per_month: f64,
+}
+
+impl Default for ElectricityConfig {
+    fn default() -> Self {
+        Self {
+            price_per_kwh: 0.12,
+            utilization: 0.80,
+            hours_per_month: 730.0,
+        }
+    }
+}
+
+impl ElectricityConfig {
+    pub fn hourly_cost(&self, tdp_w: u32) -> f64 {
+        tdp_w as f64 * self.utilization * self.price_per_kwh / 1000.0
+    }
+
+    pub fn monthly_cost(&self, tdp_w: u32) -> f64 {
+        self.hourly_cost(tdp_w) * self.hours_per_month
+    }
+}
+
+/// Assumptions behind the `need` TCO comparison beyond raw electricity price:
+/// how hard cloud rentals are actually kept busy, and how long local
+/// hardware is amortized over.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CostModelConfig {
+    pub cloud_utilization_pct: f64,
+    pub amortization_months: f64,
+}
+
+impl Default for CostModelConfig {
+    fn default() -> Self {
+        Self {
+            cloud_utilization_pct: 1.0,
+            amortization_months: 12.0,
+        }
+    }
+}
+
+/// Default selection strategy when neither `--cheapest` nor `--fastest` is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    Cheapest,
+    Fastest,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct DefaultsConfig {
+    pub lang: Option<String>,
+    pub preferred_provider: Option<String>,
+    /// `"cheapest"` or `"fastest"`; unrecognized values fall back to cheapest.
+    pub selection: Option<String>,
+    /// `"llama.cpp"` or `"mlx"`; unrecognized values fall back to llama.cpp.
+    pub default_runtime: Option<String>,
+}
+
+/// Where `hf-providers sync` fetches `hardware.toml`/`cloud.toml` from.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Base location holding `hardware.toml`/`cloud.toml`. Defaults to this
+    /// project's GitHub repo. `https://`/`http://` use a plain conditional
+    /// GET; `s3://`, `gs://`, and `az://` route through `object_store` so an
+    /// organization can host its own copies in a private bucket.
+    pub base_url: Option<String>,
+}
+
+/// A patch to apply on top of a GPU's bundled/cached spec.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GpuOverride {
+    pub street_usd: Option<u32>,
+}
+
+#[cfg(feature = "network")]
+const DEFAULT_CONFIG_TEMPLATE: &str = r#"# hf-providers config
+# See https://github.com/jadnohra/hf-providers for the full schema.
+
+[electricity]
+price_per_kwh = 0.12
+utilization = 0.80
+hours_per_month = 730.0
+
+[cost_model]
+# How hard a rented cloud GPU is actually kept busy; lower than 1.0 spreads
+# the hourly rental cost over fewer real generations.
+cloud_utilization_pct = 1.0
+# Horizon (in months) over which local hardware's street price is amortized.
+amortization_months = 12.0
+
+[defaults]
+# lang = "python"
+# preferred_provider = "together"
+# selection = "cheapest"  # or "fastest"
+# default_runtime = "llama.cpp"  # or "mlx"
+
+[sync]
+# base_url = "s3://my-bucket/hf-providers-data"
+
+[gpu_overrides]
+# rtx_4090 = { street_usd = 1500 }
+"#;
+
+impl AppConfig {
+    /// `~/.config/hf-providers/config.toml`, the default location consulted
+    /// when no `--config` override is given.
+    #[cfg(feature = "network")]
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("hf-providers").join("config.toml"))
+    }
+
+    /// Load config from `path` if given, else the default location. Creates
+    /// a commented default file at that location on first run (mirroring
+    /// tools like `bottom`'s `--config`), then returns the built-in defaults
+    /// for that first run.
+    #[cfg(feature = "network")]
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let target = match path {
+            Some(p) => Some(p.to_path_buf()),
+            None => Self::default_path(),
+        };
+        let Some(target) = target else {
+            return Ok(Self::default().with_env_overrides());
+        };
+
+        if !target.exists() {
+            if let Some(parent) = target.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let _ = std::fs::write(&target, DEFAULT_CONFIG_TEMPLATE);
+            return Ok(Self::default().with_env_overrides());
+        }
+
+        let content = std::fs::read_to_string(&target)
+            .map_err(|e| HfpError::Io(format!("failed to read {}: {e}", target.display())))?;
+        let config: Self = toml::from_str(&content)
+            .map_err(|e| HfpError::Io(format!("bad config at {}: {e}", target.display())))?;
+        Ok(config.with_env_overrides())
+    }
+
+    /// Apply `HFP_*` env var overrides on top of whatever was loaded from
+    /// the config file (env vars sit between the config file and CLI flags
+    /// in the resolution order).
+    fn with_env_overrides(mut self) -> Self {
+        if let Some(v) = env_f64("HFP_ELECTRICITY_PRICE_PER_KWH") {
+            self.electricity.price_per_kwh = v;
+        }
+        if let Some(v) = env_f64("HFP_ELECTRICITY_UTILIZATION") {
+            self.electricity.utilization = v;
+        }
+        if let Some(v) = env_f64("HFP_ELECTRICITY_HOURS_PER_MONTH") {
+            self.electricity.hours_per_month = v;
+        }
+        if let Ok(v) = std::env::var("HFP_DEFAULT_LANG") {
+            self.defaults.lang = Some(v);
+        }
+        if let Ok(v) = std::env::var("HFP_DEFAULT_PROVIDER") {
+            self.defaults.preferred_provider = Some(v);
+        }
+        if let Ok(v) = std::env::var("HFP_DEFAULT_SELECTION") {
+            self.defaults.selection = Some(v);
+        }
+        if let Ok(v) = std::env::var("HFP_DEFAULT_RUNTIME") {
+            self.defaults.default_runtime = Some(v);
+        }
+        if let Some(v) = env_f64("HFP_CLOUD_UTILIZATION_PCT") {
+            self.cost_model.cloud_utilization_pct = v;
+        }
+        if let Some(v) = env_f64("HFP_AMORTIZATION_MONTHS") {
+            self.cost_model.amortization_months = v;
+        }
+        if let Ok(v) = std::env::var("HFP_SYNC_BASE_URL") {
+            self.sync.base_url = Some(v);
+        }
+        self
+    }
+
+    /// Resolve the snippet language: CLI flag, else the configured default,
+    /// else `"python"`.
+    pub fn resolve_lang<'a>(&'a self, cli: Option<&'a str>) -> &'a str {
+        cli.or(self.defaults.lang.as_deref()).unwrap_or("python")
+    }
+
+    /// Resolve the preferred provider name: CLI flag, else the configured default.
+    pub fn resolve_provider<'a>(&'a self, cli: Option<&'a str>) -> Option<&'a str> {
+        cli.or(self.defaults.preferred_provider.as_deref())
+    }
+
+    /// Resolve cheapest-vs-fastest selection: explicit CLI flags win, else
+    /// the configured default, else cheapest.
+    pub fn resolve_selection(&self, fastest: bool, cheapest: bool) -> Selection {
+        if fastest {
+            Selection::Fastest
+        } else if cheapest {
+            Selection::Cheapest
+        } else {
+            match self.defaults.selection.as_deref() {
+                Some(s) if s.eq_ignore_ascii_case("fastest") => Selection::Fastest,
+                _ => Selection::Cheapest,
+            }
+        }
+    }
+
+    /// Resolve the default runtime for local/cloud fit estimates: the
+    /// configured default, else llama.cpp.
+    pub fn resolve_runtime(&self) -> Runtime {
+        match self.defaults.default_runtime.as_deref() {
+            Some(s) if s.eq_ignore_ascii_case("mlx") => Runtime::Mlx,
+            _ => Runtime::LlamaCpp,
+        }
+    }
+
+    /// Patch `gpus` in place with any matching `[gpu_overrides]` entries.
+    pub fn apply_gpu_overrides(&self, gpus: &mut [(String, GpuSpec)]) {
+        for (key, spec) in gpus.iter_mut() {
+            if let Some(over) = self.gpu_overrides.get(key) {
+                if let Some(street_usd) = over.street_usd {
+                    spec.street_usd = Some(street_usd);
+                }
+            }
+        }
+    }
+}
+
+fn env_f64(name: &str) -> Option<f64> {
+    std::env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_electricity_matches_prior_hardcoded_constants() {
+        let cfg = ElectricityConfig::default();
+        // Matches the values cmd_machine/cmd_need used to hardcode.
+        assert_eq!(cfg.price_per_kwh, 0.12);
+        assert_eq!(cfg.utilization, 0.80);
+        assert_eq!(cfg.hours_per_month, 730.0);
+    }
+
+    #[test]
+    fn default_cost_model_matches_prior_hardcoded_constants() {
+        let cfg = CostModelConfig::default();
+        // Matches the "floor cost at 100% utilization" cmd_need used to hardcode.
+        assert_eq!(cfg.cloud_utilization_pct, 1.0);
+        assert_eq!(cfg.amortization_months, 12.0);
+    }
+
+    #[test]
+    fn resolve_runtime_prefers_configured_default() {
+        let mut cfg = AppConfig::default();
+        assert_eq!(cfg.resolve_runtime(), Runtime::LlamaCpp);
+        cfg.defaults.default_runtime = Some("mlx".to_string());
+        assert_eq!(cfg.resolve_runtime(), Runtime::Mlx);
+        cfg.defaults.default_runtime = Some("bogus".to_string());
+        assert_eq!(cfg.resolve_runtime(), Runtime::LlamaCpp);
+    }
+
+    #[test]
+    fn monthly_cost_matches_formula() {
+        let cfg = ElectricityConfig::default();
+        let expected = 450.0_f64 * 0.80 * 730.0 / 1000.0 * 0.12;
+        assert!((cfg.monthly_cost(450) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parses_toml_with_partial_sections() {
+        let toml_str = r#"
+            [electricity]
+            price_per_kwh = 0.20
+
+            [defaults]
+            selection = "fastest"
+        "#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.electricity.price_per_kwh, 0.20);
+        assert_eq!(cfg.electricity.utilization, 0.80); // untouched default
+        assert_eq!(cfg.defaults.selection.as_deref(), Some("fastest"));
+    }
+
+    #[test]
+    fn resolve_lang_prefers_cli_over_config() {
+        let mut cfg = AppConfig::default();
+        cfg.defaults.lang = Some("curl".to_string());
+        assert_eq!(cfg.resolve_lang(Some("go")), "go");
+        assert_eq!(cfg.resolve_lang(None), "curl");
+        assert_eq!(AppConfig::default().resolve_lang(None), "python");
+    }
+
+    #[test]
+    fn resolve_selection_prefers_explicit_flags() {
+        let mut cfg = AppConfig::default();
+        cfg.defaults.selection = Some("fastest".to_string());
+        assert_eq!(cfg.resolve_selection(false, true), Selection::Cheapest);
+        assert_eq!(cfg.resolve_selection(false, false), Selection::Fastest);
+    }
+
+    #[test]
+    fn apply_gpu_overrides_patches_matching_key_only() {
+        let cfg_toml = r#"
+            [gpu_overrides]
+            rtx_4090 = { street_usd = 1500 }
+        "#;
+        let cfg: AppConfig = toml::from_str(cfg_toml).unwrap();
+        let mut gpus = vec![
+            ("rtx_4090".to_string(), test_gpu(1000)),
+            ("h100".to_string(), test_gpu(25000)),
+        ];
+        cfg.apply_gpu_overrides(&mut gpus);
+        assert_eq!(gpus[0].1.street_usd, Some(1500));
+        assert_eq!(gpus[1].1.street_usd, Some(25000));
+    }
+
+    fn test_gpu(street_usd: u32) -> GpuSpec {
+        GpuSpec {
+            name: "test".to_string(),
+            vendor: "nvidia".to_string(),
+            arch: "test".to_string(),
+            vram_gb: 24.0,
+            mem_bw_gb_s: 1000.0,
+            fp16_tflops: 100.0,
+            tdp_w: 350,
+            street_usd: Some(street_usd),
+            llamacpp_decode_eff: 0.5,
+            llamacpp_prefill_eff: 0.5,
+            mlx_decode_eff: None,
+            mlx_prefill_eff: None,
+            host_ram_gb: None,
+            host_bw_gb_s: None,
+        }
+    }
+}
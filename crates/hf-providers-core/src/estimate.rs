@@ -1,40 +1,199 @@
 use crate::hardware::{GpuSpec, Runtime};
 
 /// Quantization level for weight storage.
+///
+/// Variants are ordered ascending by quality/size so callers can walk the
+/// ladder from smallest to largest footprint.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Quant {
+    Q2K,
+    Q3K,
     Q4,
+    Q5K,
+    Q6K,
     Q8,
+    FP8,
     FP16,
 }
 
+/// Ascending ladder of quants, smallest footprint first.
+pub const QUANT_LADDER: [Quant; 8] = [
+    Quant::Q2K,
+    Quant::Q3K,
+    Quant::Q4,
+    Quant::Q5K,
+    Quant::Q6K,
+    Quant::Q8,
+    Quant::FP8,
+    Quant::FP16,
+];
+
 impl Quant {
+    /// Effective bytes/param, including per-block scale/zero-point overhead
+    /// for the k-quant variants (these aren't clean power-of-two fractions).
+    /// Bytes per weight, derived from each GGUF quant's bits-per-weight
+    /// (~2.6/3.4/5.5/6.6 bits for the K-quants, per llama.cpp's block layout).
     pub fn bytes_per_param(self) -> f64 {
         match self {
+            Quant::Q2K => 0.325,
+            Quant::Q3K => 0.425,
             Quant::Q4 => 0.5,
+            Quant::Q5K => 0.6875,
+            Quant::Q6K => 0.825,
             Quant::Q8 => 1.0,
+            Quant::FP8 => 1.0,
             Quant::FP16 => 2.0,
         }
     }
 
+    /// Canonical label as it appears on model cards / GGUF filenames.
     pub fn label(self) -> &'static str {
         match self {
+            Quant::Q2K => "Q2_K",
+            Quant::Q3K => "Q3_K",
             Quant::Q4 => "Q4",
+            Quant::Q5K => "Q5_K",
+            Quant::Q6K => "Q6_K",
             Quant::Q8 => "Q8",
+            Quant::FP8 => "FP8",
             Quant::FP16 => "FP16",
         }
     }
 }
 
+/// Model/runtime geometry needed to size the KV cache for a specific workload.
+/// Defaults assume a modest GQA chat workload when callers don't know the
+/// model's real attention geometry.
+#[derive(Debug, Clone, Copy)]
+pub struct ContextSpec {
+    /// Context length in tokens.
+    pub seq_len: u64,
+    /// Batch size (concurrent sequences).
+    pub batch: u32,
+    pub n_layers: u32,
+    pub n_kv_heads: u32,
+    pub head_dim: u32,
+    /// Precision the KV cache is stored at (independent of weight quant).
+    pub kv_quant: Quant,
+}
+
+impl Default for ContextSpec {
+    fn default() -> Self {
+        Self {
+            seq_len: 4096,
+            batch: 1,
+            n_layers: 32,
+            n_kv_heads: 8,
+            head_dim: 128,
+            kv_quant: Quant::FP16,
+        }
+    }
+}
+
+impl ContextSpec {
+    /// KV cache size in GB: `2 * n_layers * n_kv_heads * head_dim * seq_len * batch * bytes_per_elem`.
+    /// The factor of 2 covers both K and V.
+    pub fn kv_gb(&self) -> f64 {
+        let kv_bytes = 2.0
+            * self.n_layers as f64
+            * self.n_kv_heads as f64
+            * self.head_dim as f64
+            * self.seq_len as f64
+            * self.batch as f64
+            * self.kv_quant.bytes_per_param();
+        kv_bytes / 1e9
+    }
+
+    /// Approximate a `ContextSpec` from just `params` and a context length,
+    /// for callers (e.g. the WASM bindings) that don't know a model's real
+    /// attention geometry. Scales `n_layers` with `params^(1/3)` off the
+    /// generic-GQA baseline (an 8B-class model ≈ 32 layers) and keeps
+    /// `n_kv_heads`/`head_dim` at the same typical GQA values as `Default`.
+    pub fn approx_for_params(params: u64, seq_len: u64) -> Self {
+        const BASELINE_PARAMS: f64 = 8e9;
+        const BASELINE_LAYERS: f64 = 32.0;
+        let scale = (params as f64 / BASELINE_PARAMS).powf(1.0 / 3.0);
+        let n_layers = (BASELINE_LAYERS * scale).round().max(1.0) as u32;
+        Self {
+            seq_len,
+            n_layers,
+            ..Self::default()
+        }
+    }
+}
+
 /// Whether the model fits in GPU VRAM.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Fit {
     /// Fits entirely in VRAM.
     Full,
-    /// Too large for VRAM.
+    /// Weights exceed usable VRAM but fit in VRAM + host RAM, with
+    /// `cpu_layers` of the `gpu_layers + cpu_layers` total offloaded to the host.
+    Partial { gpu_layers: u32, cpu_layers: u32 },
+    /// Too large even with host RAM offload.
     NoFit,
 }
 
+/// Decide whether (and how) a model fits, given usable VRAM and optional host
+/// RAM for CPU-offloaded layers.
+fn resolve_fit(weight_gb: f64, usable_vram: f64, host_ram_gb: Option<f64>, n_layers: u32) -> Fit {
+    if weight_gb <= usable_vram {
+        return Fit::Full;
+    }
+    if let Some(host_ram_gb) = host_ram_gb {
+        if weight_gb <= usable_vram + host_ram_gb {
+            let frac = (usable_vram / weight_gb).clamp(0.0, 1.0);
+            let gpu_layers = (frac * n_layers as f64).round() as u32;
+            let cpu_layers = n_layers.saturating_sub(gpu_layers);
+            return Fit::Partial { gpu_layers, cpu_layers };
+        }
+    }
+    Fit::NoFit
+}
+
+/// Batch size at which decode throughput crosses from memory-bandwidth-bound
+/// to compute-bound, i.e. where `batch * r_mem_per_step == r_flops`.
+fn decode_crossover_batch(r_mem_per_step: f64, r_flops: f64) -> f64 {
+    if r_mem_per_step > 0.0 {
+        r_flops / r_mem_per_step
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Decode throughput (tokens/sec, summed across the batch) for a resolved
+/// fit, applying the roofline `min` of the memory-bound and compute-bound
+/// regimes. `None` when the model doesn't fit at all, or when a partial fit
+/// can't be modeled (no host bandwidth known).
+fn decode_tok_s_for_fit(
+    fit: &Fit,
+    gpu: &GpuSpec,
+    weight_gb: f64,
+    decode_eff: f64,
+    params: u64,
+    batch: u32,
+) -> Option<f64> {
+    let r_flops = gpu.fp16_tflops * 1e12 * decode_eff / (2.0 * params as f64);
+    match fit {
+        Fit::NoFit => None,
+        Fit::Full => {
+            let r_mem_per_step = gpu.mem_bw_gb_s * decode_eff / weight_gb;
+            Some((batch as f64 * r_mem_per_step).min(r_flops))
+        }
+        Fit::Partial { gpu_layers, cpu_layers } => {
+            let total = (*gpu_layers + *cpu_layers).max(1) as f64;
+            let f = *gpu_layers as f64 / total;
+            let host_bw_gb_s = gpu.host_bw_gb_s?;
+            // Harmonic blend: GPU-resident layers stream from VRAM, offloaded
+            // layers stream across the host link, bounded by the slower tier.
+            let gpu_rate = gpu.mem_bw_gb_s * decode_eff / weight_gb;
+            let host_rate = host_bw_gb_s / weight_gb;
+            let r_mem_per_step = 1.0 / (f / gpu_rate + (1.0 - f) / host_rate);
+            Some((batch as f64 * r_mem_per_step).min(r_flops))
+        }
+    }
+}
+
 /// Performance estimate for a model on a specific GPU at a given quantization.
 #[derive(Debug, Clone)]
 pub struct Estimate {
@@ -42,39 +201,39 @@ pub struct Estimate {
     pub gpu_name: String,
     pub quant: Quant,
     pub weight_gb: f64,
+    pub kv_gb: f64,
     pub fit: Fit,
     pub decode_tok_s: Option<f64>,
     pub prefill_tok_s: Option<f64>,
+    /// Batch size at which decode throughput transitions from
+    /// memory-bandwidth-bound to compute-bound. Below this batch, adding more
+    /// concurrent sequences grows throughput roughly linearly; above it,
+    /// decode is clamped by the compute roofline.
+    pub decode_crossover_batch: f64,
 }
 
-/// Overhead fraction for framework/KV cache/activations.
-const VRAM_OVERHEAD: f64 = 0.15;
+/// Fixed allocator/CUDA-context overhead that doesn't scale with context length.
+const FIXED_FRAMEWORK_OVERHEAD_GB: f64 = 1.0;
 
 /// Estimate performance of a model (given its total param count) on a GPU.
-pub fn estimate(gpu: &GpuSpec, params: u64, quant: Quant, runtime: Runtime) -> Estimate {
+pub fn estimate(gpu: &GpuSpec, params: u64, quant: Quant, runtime: Runtime, ctx: &ContextSpec) -> Estimate {
     let weight_gb = params as f64 * quant.bytes_per_param() / 1e9;
-    let usable_vram = gpu.vram_gb * (1.0 - VRAM_OVERHEAD);
+    let kv_gb = ctx.kv_gb();
+    let usable_vram = (gpu.vram_gb - kv_gb - FIXED_FRAMEWORK_OVERHEAD_GB).max(0.0);
 
-    let fit = if weight_gb <= usable_vram {
-        Fit::Full
-    } else {
-        Fit::NoFit
-    };
+    let fit = resolve_fit(weight_gb, usable_vram, gpu.host_ram_gb, ctx.n_layers);
 
     let decode_eff = gpu.decode_eff(runtime);
     let prefill_eff = gpu.prefill_eff(runtime);
 
-    let decode_tok_s = match &fit {
-        Fit::NoFit => None,
-        Fit::Full => {
-            let tok_s = gpu.mem_bw_gb_s * decode_eff / weight_gb;
-            Some(tok_s)
-        }
-    };
+    let decode_tok_s = decode_tok_s_for_fit(&fit, gpu, weight_gb, decode_eff, params, ctx.batch);
+    let r_mem_per_step = gpu.mem_bw_gb_s * decode_eff / weight_gb;
+    let r_flops = gpu.fp16_tflops * 1e12 * decode_eff / (2.0 * params as f64);
+    let decode_crossover_batch = decode_crossover_batch(r_mem_per_step, r_flops);
 
     let prefill_tok_s = match &fit {
         Fit::NoFit => None,
-        Fit::Full => {
+        Fit::Full | Fit::Partial { .. } => {
             let params_f = params as f64;
             let tok_s = gpu.fp16_tflops * 1e12 * prefill_eff / (2.0 * params_f);
             Some(tok_s)
@@ -86,9 +245,11 @@ pub fn estimate(gpu: &GpuSpec, params: u64, quant: Quant, runtime: Runtime) -> E
         gpu_name: gpu.name.clone(),
         quant,
         weight_gb,
+        kv_gb,
         fit,
         decode_tok_s,
         prefill_tok_s,
+        decode_crossover_batch,
     }
 }
 
@@ -100,9 +261,11 @@ pub fn estimate_multi_gpu(
     quant: Quant,
     runtime: Runtime,
     gpu_count: u32,
+    ctx: &ContextSpec,
 ) -> Estimate {
     let weight_gb = params as f64 * quant.bytes_per_param() / 1e9;
-    let usable_vram = gpu.vram_gb * gpu_count as f64 * (1.0 - VRAM_OVERHEAD);
+    let kv_gb = ctx.kv_gb();
+    let usable_vram = (gpu.vram_gb * gpu_count as f64 - kv_gb - FIXED_FRAMEWORK_OVERHEAD_GB).max(0.0);
 
     let fit = if weight_gb <= usable_vram {
         Fit::Full
@@ -114,19 +277,91 @@ pub fn estimate_multi_gpu(
     let decode_eff = gpu.decode_eff(runtime);
     let prefill_eff = gpu.prefill_eff(runtime);
 
+    let r_mem_per_step = gpu.mem_bw_gb_s * decode_eff * n / weight_gb;
+    let r_flops = gpu.fp16_tflops * 1e12 * decode_eff * n / (2.0 * params as f64);
+    let decode_crossover_batch = decode_crossover_batch(r_mem_per_step, r_flops);
+
     let decode_tok_s = match &fit {
-        Fit::NoFit => None,
+        Fit::NoFit | Fit::Partial { .. } => None,
+        Fit::Full => Some((ctx.batch as f64 * r_mem_per_step).min(r_flops)),
+    };
+
+    let prefill_tok_s = match &fit {
+        Fit::NoFit | Fit::Partial { .. } => None,
         Fit::Full => {
-            let tok_s = gpu.mem_bw_gb_s * decode_eff * n / weight_gb;
+            let params_f = params as f64;
+            let tok_s = gpu.fp16_tflops * 1e12 * prefill_eff * n / (2.0 * params_f);
             Some(tok_s)
         }
     };
 
+    Estimate {
+        gpu_key: String::new(),
+        gpu_name: gpu.name.clone(),
+        quant,
+        weight_gb,
+        kv_gb,
+        fit,
+        decode_tok_s,
+        prefill_tok_s,
+        decode_crossover_batch,
+    }
+}
+
+/// Effective-bandwidth retention factor for each GPU added beyond the first,
+/// modeling the communication overhead of tensor-parallel decode. NVLink's
+/// high-bandwidth fabric keeps most of each additional GPU's throughput;
+/// PCIe pays a steeper tax since all-reduce traffic contends with the host link.
+fn interconnect_factor(interconnect: Option<&str>) -> f64 {
+    match interconnect.map(|s| s.eq_ignore_ascii_case("nvlink")) {
+        Some(true) => 0.9,
+        _ => 0.7,
+    }
+}
+
+/// Estimate performance on a multi-GPU setup, accounting for interconnect
+/// ("nvlink" vs "pcie", as carried by `CloudOffering::interconnect`). VRAM
+/// still pools linearly for the fit check, but throughput scales with an
+/// effective GPU count that discounts each GPU beyond the first by the
+/// interconnect's communication-overhead factor.
+pub fn estimate_multi(
+    gpu: &GpuSpec,
+    gpu_count: u32,
+    interconnect: Option<&str>,
+    params: u64,
+    quant: Quant,
+    runtime: Runtime,
+    ctx: &ContextSpec,
+) -> Estimate {
+    let weight_gb = params as f64 * quant.bytes_per_param() / 1e9;
+    let kv_gb = ctx.kv_gb();
+    let usable_vram = (gpu.vram_gb * gpu_count as f64 - kv_gb - FIXED_FRAMEWORK_OVERHEAD_GB).max(0.0);
+
+    let fit = if weight_gb <= usable_vram {
+        Fit::Full
+    } else {
+        Fit::NoFit
+    };
+
+    let factor = interconnect_factor(interconnect);
+    let effective_n = 1.0 + (gpu_count.saturating_sub(1) as f64) * factor;
+
+    let decode_eff = gpu.decode_eff(runtime);
+    let prefill_eff = gpu.prefill_eff(runtime);
+
+    let r_mem_per_step = gpu.mem_bw_gb_s * decode_eff * effective_n / weight_gb;
+    let r_flops = gpu.fp16_tflops * 1e12 * decode_eff * effective_n / (2.0 * params as f64);
+    let decode_crossover_batch = decode_crossover_batch(r_mem_per_step, r_flops);
+
+    let decode_tok_s = match &fit {
+        Fit::NoFit | Fit::Partial { .. } => None,
+        Fit::Full => Some((ctx.batch as f64 * r_mem_per_step).min(r_flops)),
+    };
+
     let prefill_tok_s = match &fit {
-        Fit::NoFit => None,
+        Fit::NoFit | Fit::Partial { .. } => None,
         Fit::Full => {
-            let params_f = params as f64;
-            let tok_s = gpu.fp16_tflops * 1e12 * prefill_eff * n / (2.0 * params_f);
+            let tok_s = gpu.fp16_tflops * 1e12 * prefill_eff * effective_n / (2.0 * params as f64);
             Some(tok_s)
         }
     };
@@ -136,38 +371,83 @@ pub fn estimate_multi_gpu(
         gpu_name: gpu.name.clone(),
         quant,
         weight_gb,
+        kv_gb,
         fit,
         decode_tok_s,
         prefill_tok_s,
+        decode_crossover_batch,
+    }
+}
+
+/// Pick the highest-fidelity quantization that still fits a multi-GPU setup,
+/// accounting for interconnect (see `estimate_multi`).
+pub fn best_quant_multi(
+    gpu: &GpuSpec,
+    gpu_count: u32,
+    interconnect: Option<&str>,
+    params: u64,
+    runtime: Runtime,
+    ctx: &ContextSpec,
+) -> Option<(Quant, Estimate)> {
+    let mut best: Option<(Quant, Estimate)> = None;
+    for q in QUANT_LADDER {
+        let est = estimate_multi(gpu, gpu_count, interconnect, params, q, runtime, ctx);
+        if est.fit != Fit::Full {
+            break;
+        }
+        best = Some((q, est));
     }
+    best
 }
 
-/// Pick the best quantization for a multi-GPU setup.
+/// Pick the highest-fidelity quantization that still fits a multi-GPU setup.
 pub fn best_quant_multi_gpu(
     gpu: &GpuSpec,
     params: u64,
     runtime: Runtime,
     gpu_count: u32,
+    ctx: &ContextSpec,
 ) -> Option<(Quant, Estimate)> {
-    for q in [Quant::Q4, Quant::Q8, Quant::FP16] {
-        let est = estimate_multi_gpu(gpu, params, q, runtime, gpu_count);
-        if est.fit == Fit::Full {
-            return Some((q, est));
+    let mut best: Option<(Quant, Estimate)> = None;
+    for q in QUANT_LADDER {
+        let est = estimate_multi_gpu(gpu, params, q, runtime, gpu_count, ctx);
+        if est.fit != Fit::Full {
+            break;
         }
+        best = Some((q, est));
     }
-    None
+    best
 }
 
-/// Pick the best quantization level that fits a GPU for a given model.
-/// Tries Q4 first, then Q8, then FP16.
-pub fn best_quant(gpu: &GpuSpec, params: u64, runtime: Runtime) -> Option<(Quant, Estimate)> {
-    for q in [Quant::Q4, Quant::Q8, Quant::FP16] {
-        let est = estimate(gpu, params, q, runtime);
-        if est.fit == Fit::Full {
-            return Some((q, est));
+/// Pick the highest-fidelity quantization that still fits a GPU for a given
+/// model, walking the ladder from smallest footprint (Q2_K) up to FP16.
+///
+/// A fully VRAM-resident quant is always preferred over one that spills
+/// layers to the CPU, even if a higher-fidelity quant further up the ladder
+/// would technically fit via offload: CPU offload tanks decode throughput,
+/// so a lower-fidelity `Full` fit beats a higher-fidelity `Partial` one. Only
+/// when nothing fits fully does this fall back to the fastest `Partial` fit
+/// seen (offload gets slower, not faster, as the ladder climbs, so that's
+/// whichever one was found first).
+pub fn best_quant(gpu: &GpuSpec, params: u64, runtime: Runtime, ctx: &ContextSpec) -> Option<(Quant, Estimate)> {
+    let mut best_full: Option<(Quant, Estimate)> = None;
+    let mut best_partial: Option<(Quant, Estimate)> = None;
+    for q in QUANT_LADDER {
+        let est = estimate(gpu, params, q, runtime, ctx);
+        match est.fit {
+            Fit::NoFit => break, // Footprint only grows up the ladder, so nothing larger will fit either.
+            Fit::Full => best_full = Some((q, est)),
+            Fit::Partial { .. } => {
+                let is_faster = best_partial
+                    .as_ref()
+                    .map_or(true, |(_, prev)| est.decode_tok_s.unwrap_or(0.0) > prev.decode_tok_s.unwrap_or(0.0));
+                if is_faster {
+                    best_partial = Some((q, est));
+                }
+            }
         }
     }
-    None
+    best_full.or(best_partial)
 }
 
 #[cfg(test)]
@@ -180,10 +460,14 @@ mod tests {
         gpus.into_iter().find(|(k, _)| k == key).unwrap().1
     }
 
+    fn ctx() -> ContextSpec {
+        ContextSpec::default()
+    }
+
     // 8B model at Q4 on RTX 4090: should fit, ~100-170 tok/s decode
     #[test]
     fn llama_8b_q4_rtx4090() {
-        let est = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp);
+        let est = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert_eq!(est.fit, Fit::Full);
         assert!(est.weight_gb < 5.0, "8B Q4 should be ~4 GB");
         let d = est.decode_tok_s.unwrap();
@@ -194,7 +478,7 @@ mod tests {
     // 70B at Q4 on RTX 4090 (24GB): doesn't fit (~35GB model in 24GB)
     #[test]
     fn llama_70b_q4_rtx4090_nofit() {
-        let est = estimate(&gpu("rtx_4090"), 70_600_000_000, Quant::Q4, Runtime::LlamaCpp);
+        let est = estimate(&gpu("rtx_4090"), 70_600_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert_eq!(est.fit, Fit::NoFit);
         assert!(est.decode_tok_s.is_none());
     }
@@ -202,7 +486,7 @@ mod tests {
     // 70B at Q4 on M4 Max 128GB with mlx: should fit comfortably
     #[test]
     fn llama_70b_q4_m4max128_fits_mlx() {
-        let est = estimate(&gpu("m4_max_128"), 70_600_000_000, Quant::Q4, Runtime::Mlx);
+        let est = estimate(&gpu("m4_max_128"), 70_600_000_000, Quant::Q4, Runtime::Mlx, &ctx());
         assert_eq!(est.fit, Fit::Full);
         let d = est.decode_tok_s.unwrap();
         assert!(d > 5.0 && d < 40.0, "decode {d:.1} out of range for 70B Q4 on M4 Max (mlx)");
@@ -212,8 +496,8 @@ mod tests {
     #[test]
     fn mlx_faster_than_llamacpp_on_apple() {
         let g = gpu("m4_max_128");
-        let mlx = estimate(&g, 8_000_000_000, Quant::Q4, Runtime::Mlx);
-        let lcpp = estimate(&g, 8_000_000_000, Quant::Q4, Runtime::LlamaCpp);
+        let mlx = estimate(&g, 8_000_000_000, Quant::Q4, Runtime::Mlx, &ctx());
+        let lcpp = estimate(&g, 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert!(
             mlx.decode_tok_s.unwrap() > lcpp.decode_tok_s.unwrap(),
             "mlx ({:.1}) should be faster than llama.cpp ({:.1}) on Apple",
@@ -225,7 +509,7 @@ mod tests {
     // 671B (DeepSeek-R1) at Q4 on RTX 4090: way too large
     #[test]
     fn deepseek_r1_q4_rtx4090_nofit() {
-        let est = estimate(&gpu("rtx_4090"), 671_000_000_000, Quant::Q4, Runtime::LlamaCpp);
+        let est = estimate(&gpu("rtx_4090"), 671_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert_eq!(est.fit, Fit::NoFit);
         assert!(est.decode_tok_s.is_none());
     }
@@ -233,31 +517,54 @@ mod tests {
     // 671B at Q4 on M4 Max 128GB: 335 GB > 128 GB, no fit
     #[test]
     fn deepseek_r1_q4_m4max128_nofit() {
-        let est = estimate(&gpu("m4_max_128"), 671_000_000_000, Quant::Q4, Runtime::Mlx);
+        let est = estimate(&gpu("m4_max_128"), 671_000_000_000, Quant::Q4, Runtime::Mlx, &ctx());
         assert_eq!(est.fit, Fit::NoFit);
     }
 
-    // 8B model: best_quant should pick Q4 (smallest that fits)
+    // 8B model comfortably fits at FP16 on a 4090: best_quant should walk the
+    // ladder all the way up to the highest fidelity that still fits.
     #[test]
-    fn best_quant_picks_q4_for_small_model() {
-        let (q, est) = best_quant(&gpu("rtx_4090"), 8_000_000_000, Runtime::LlamaCpp).unwrap();
-        assert_eq!(q, Quant::Q4);
+    fn best_quant_picks_highest_fidelity_that_fits() {
+        let (q, est) = best_quant(&gpu("rtx_4090"), 8_000_000_000, Runtime::LlamaCpp, &ctx()).unwrap();
+        assert_eq!(q, Quant::FP16);
+        assert_eq!(est.fit, Fit::Full);
+    }
+
+    // 65B model: Q3_K (~27.6 GB) doesn't fit a 24 GB 4090, but Q2_K (~21 GB) does.
+    #[test]
+    fn best_quant_picks_lower_fidelity_when_needed() {
+        let (q, est) = best_quant(&gpu("rtx_4090"), 65_000_000_000, Runtime::LlamaCpp, &ctx()).unwrap();
+        assert_eq!(q, Quant::Q2K);
         assert_eq!(est.fit, Fit::Full);
     }
 
     // Huge model: best_quant returns None when nothing fits
     #[test]
     fn best_quant_none_for_huge_model() {
-        assert!(best_quant(&gpu("rtx_4090"), 671_000_000_000, Runtime::LlamaCpp).is_none());
+        assert!(best_quant(&gpu("rtx_4090"), 671_000_000_000, Runtime::LlamaCpp, &ctx()).is_none());
+    }
+
+    // With host_ram_gb set, Q3_K/Q4 of this 65B model fit via CPU offload
+    // (Partial) but Q2_K fits entirely in VRAM (Full). best_quant should
+    // prefer the fully VRAM-resident Q2_K over a higher-fidelity but
+    // CPU-offloaded and therefore slower quant.
+    #[test]
+    fn best_quant_prefers_full_fit_over_higher_fidelity_partial() {
+        let mut g = gpu("rtx_4090");
+        g.host_ram_gb = Some(64.0);
+        g.host_bw_gb_s = Some(32.0);
+        let (q, est) = best_quant(&g, 65_000_000_000, Runtime::LlamaCpp, &ctx()).unwrap();
+        assert_eq!(q, Quant::Q2K);
+        assert_eq!(est.fit, Fit::Full);
     }
 
     // Verify weight_gb calculation
     #[test]
     fn weight_gb_math() {
-        let est = estimate(&gpu("rtx_4090"), 70_000_000_000, Quant::Q4, Runtime::LlamaCpp);
+        let est = estimate(&gpu("rtx_4090"), 70_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert!((est.weight_gb - 35.0).abs() < 0.1, "70B Q4 = 35 GB");
 
-        let est = estimate(&gpu("rtx_4090"), 70_000_000_000, Quant::FP16, Runtime::LlamaCpp);
+        let est = estimate(&gpu("rtx_4090"), 70_000_000_000, Quant::FP16, Runtime::LlamaCpp, &ctx());
         assert!((est.weight_gb - 140.0).abs() < 0.1, "70B FP16 = 140 GB");
     }
 
@@ -265,8 +572,8 @@ mod tests {
     #[test]
     fn faster_gpu_faster_decode() {
         let params = 8_000_000_000u64;
-        let est_4090 = estimate(&gpu("rtx_4090"), params, Quant::Q4, Runtime::LlamaCpp);
-        let est_3090 = estimate(&gpu("rtx_3090"), params, Quant::Q4, Runtime::LlamaCpp);
+        let est_4090 = estimate(&gpu("rtx_4090"), params, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        let est_3090 = estimate(&gpu("rtx_3090"), params, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert!(
             est_4090.decode_tok_s.unwrap() > est_3090.decode_tok_s.unwrap(),
             "4090 should decode faster than 3090"
@@ -277,11 +584,161 @@ mod tests {
     #[test]
     fn more_compute_faster_prefill() {
         let params = 8_000_000_000u64;
-        let est_h100 = estimate(&gpu("h100_sxm5_80_gb"), params, Quant::Q4, Runtime::LlamaCpp);
-        let est_4090 = estimate(&gpu("rtx_4090"), params, Quant::Q4, Runtime::LlamaCpp);
+        let est_h100 = estimate(&gpu("h100_sxm5_80_gb"), params, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        let est_4090 = estimate(&gpu("rtx_4090"), params, Quant::Q4, Runtime::LlamaCpp, &ctx());
         assert!(
             est_h100.prefill_tok_s.unwrap() > est_4090.prefill_tok_s.unwrap(),
             "H100 should prefill faster than 4090"
         );
     }
+
+    // KV cache scales with context length and shows up in kv_gb.
+    #[test]
+    fn kv_gb_scales_with_seq_len() {
+        let short = ContextSpec { seq_len: 4_096, ..ContextSpec::default() };
+        let long = ContextSpec { seq_len: 128_000, ..ContextSpec::default() };
+        let est_short = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &short);
+        let est_long = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &long);
+        assert!(est_long.kv_gb > est_short.kv_gb);
+    }
+
+    #[test]
+    fn approx_for_params_matches_default_at_baseline() {
+        let approx = ContextSpec::approx_for_params(8_000_000_000, 4_096);
+        let default = ContextSpec::default();
+        assert_eq!(approx.n_layers, default.n_layers);
+        assert!((approx.kv_gb() - default.kv_gb()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn approx_for_params_grows_with_model_size() {
+        let small = ContextSpec::approx_for_params(8_000_000_000, 4_096);
+        let large = ContextSpec::approx_for_params(70_000_000_000, 4_096);
+        assert!(large.n_layers > small.n_layers);
+        assert!(large.kv_gb() > small.kv_gb());
+    }
+
+    // A GQA model (few KV heads) at long context should still fit where an
+    // MHA-equivalent model (n_kv_heads == n_heads) would not.
+    #[test]
+    fn gqa_fits_longer_context_than_mha() {
+        let gqa = ContextSpec {
+            seq_len: 128_000,
+            batch: 1,
+            n_layers: 32,
+            n_kv_heads: 8,
+            head_dim: 128,
+            kv_quant: Quant::FP16,
+        };
+        let mha = ContextSpec { n_kv_heads: 32, ..gqa };
+        let est_gqa = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &gqa);
+        let est_mha = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &mha);
+        assert_eq!(est_gqa.fit, Fit::Full, "GQA model should fit 128k context on a 4090");
+        assert_eq!(est_mha.fit, Fit::NoFit, "MHA-equivalent model should not fit 128k context on a 4090");
+    }
+
+    // A GPU with host_ram_gb/host_bw_gb_s set should partially fit a model
+    // that's too big for VRAM alone but fits once host RAM is counted.
+    #[test]
+    fn partial_fit_with_cpu_offload() {
+        let mut g = gpu("rtx_4090");
+        g.host_ram_gb = Some(64.0);
+        g.host_bw_gb_s = Some(32.0); // PCIe4 x16-ish
+        // 70B Q4 = 35 GB, doesn't fit 24 GB VRAM alone, but fits 24 + 64 GB.
+        let est = estimate(&g, 70_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        match est.fit {
+            Fit::Partial { gpu_layers, cpu_layers } => {
+                assert!(gpu_layers > 0 && cpu_layers > 0);
+                assert_eq!(gpu_layers + cpu_layers, ctx().n_layers);
+            }
+            other => panic!("expected Partial fit, got {other:?}"),
+        }
+        let d = est.decode_tok_s.expect("partial fit should still estimate decode tok/s");
+        // Blended throughput should sit below the all-VRAM rate for this model.
+        assert!(d > 0.0 && d < g.mem_bw_gb_s * g.llamacpp_decode_eff / est.weight_gb);
+    }
+
+    // Without host_ram_gb set, an oversized model still reports NoFit.
+    #[test]
+    fn no_host_ram_means_nofit_not_partial() {
+        let est = estimate(&gpu("rtx_4090"), 70_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        assert_eq!(est.fit, Fit::NoFit);
+    }
+
+    // Below the crossover batch, decode throughput should scale roughly
+    // linearly with batch (memory-bound regime).
+    #[test]
+    fn decode_tok_s_rises_with_batch_below_crossover() {
+        let low_batch = ContextSpec { batch: 1, ..ContextSpec::default() };
+        let mid_batch = ContextSpec { batch: 4, ..ContextSpec::default() };
+        let est_low = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &low_batch);
+        let est_mid = estimate(&gpu("rtx_4090"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &mid_batch);
+        assert!(est_mid.decode_crossover_batch > 1.0, "an 8B model on a 4090 shouldn't be compute-bound at batch 1");
+        assert!(
+            est_mid.decode_tok_s.unwrap() > est_low.decode_tok_s.unwrap(),
+            "throughput should rise with batch below the crossover"
+        );
+    }
+
+    // Far above the crossover batch, decode throughput should clamp at the
+    // compute roofline rather than keep scaling linearly.
+    #[test]
+    fn decode_tok_s_clamps_at_compute_roofline_above_crossover() {
+        let g = gpu("rtx_4090");
+        let params = 8_000_000_000u64;
+        let est1 = estimate(&g, params, Quant::Q4, Runtime::LlamaCpp, &ContextSpec { batch: 1, ..ContextSpec::default() });
+        let crossover = est1.decode_crossover_batch;
+        // A one-token context keeps the batch-scaled KV cache small enough
+        // that the model still fits at this batch size.
+        let huge_batch = ContextSpec { batch: (crossover as u32).saturating_mul(100).max(1000), seq_len: 1, ..ContextSpec::default() };
+        let est_huge = estimate(&g, params, Quant::Q4, Runtime::LlamaCpp, &huge_batch);
+        let decode_eff = g.decode_eff(Runtime::LlamaCpp);
+        let r_flops = g.fp16_tflops * 1e12 * decode_eff / (2.0 * params as f64);
+        assert!(
+            (est_huge.decode_tok_s.unwrap() - r_flops).abs() < 1.0,
+            "at a huge batch, decode tok/s should clamp at the compute roofline"
+        );
+    }
+
+    // A 671B model doesn't fit a single H100, but pools across 8 NVLink
+    // H100s and reports a realistic (non-zero, sub-linear) decode rate.
+    #[test]
+    fn estimate_multi_fits_huge_model_across_nvlink_gpus() {
+        let h100 = gpu("h100_sxm5_80_gb");
+        let single = estimate(&h100, 671_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        assert_eq!(single.fit, Fit::NoFit, "671B Q4 shouldn't fit a single H100");
+
+        let multi = estimate_multi(&h100, 8, Some("nvlink"), 671_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        assert_eq!(multi.fit, Fit::Full, "671B Q4 should fit 8x80GB H100 NVLink");
+        assert!(multi.decode_tok_s.unwrap() > 0.0);
+    }
+
+    // PCIe should scale decode throughput worse than NVLink for the same GPU
+    // count, since tensor-parallel decode is communication-bound.
+    #[test]
+    fn nvlink_scales_better_than_pcie() {
+        let h100 = gpu("h100_sxm5_80_gb");
+        let nvlink = estimate_multi(&h100, 8, Some("nvlink"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        let pcie = estimate_multi(&h100, 8, Some("pcie"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        assert!(nvlink.decode_tok_s.unwrap() > pcie.decode_tok_s.unwrap());
+    }
+
+    // A single GPU should behave the same regardless of interconnect.
+    #[test]
+    fn single_gpu_ignores_interconnect() {
+        let h100 = gpu("h100_sxm5_80_gb");
+        let a = estimate_multi(&h100, 1, Some("pcie"), 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        let b = estimate_multi(&h100, 1, None, 8_000_000_000, Quant::Q4, Runtime::LlamaCpp, &ctx());
+        assert_eq!(a.decode_tok_s, b.decode_tok_s);
+    }
+
+    #[test]
+    fn best_quant_multi_picks_highest_fidelity_that_fits_across_nvlink_gpus() {
+        let a100 = gpu("a100_pcie_80_gb");
+        // A 70B model at FP16 (140 GB) doesn't fit one A100, but fits across 4 pooled over NVLink.
+        let (q, est) = best_quant_multi(&a100, 4, Some("nvlink"), 70_000_000_000, Runtime::LlamaCpp, &ctx())
+            .expect("should fit across 4 GPUs");
+        assert_eq!(est.fit, Fit::Full);
+        assert_eq!(q, Quant::FP16);
+    }
 }
@@ -0,0 +1,387 @@
+use crate::error::{HfpError, Result};
+use crate::model::Model;
+use crate::provider::{ProviderInfo, ProviderStatus};
+
+/// Find every `(model, provider)` pair across `models` whose provider
+/// matches the filter expression `expr`, e.g.
+/// `"tools = true AND input_price < 1.0 AND context_window >= 32000"`.
+///
+/// Comparisons (`=`, `!=`, `<`, `<=`, `>`, `>=`) combine with `AND`/`OR`
+/// (`AND` binds tighter) and parentheses. A field absent on a given provider
+/// (e.g. no `input_price` reported) never matches, rather than erroring.
+pub fn filter_providers<'a>(models: &'a [Model], expr: &str) -> Result<Vec<(&'a Model, &'a ProviderInfo)>> {
+    let filter = parse(expr)?;
+    Ok(models
+        .iter()
+        .flat_map(|m| m.providers.iter().map(move |p| (m, p)))
+        .filter(|(_, p)| filter.matches(p))
+        .collect())
+}
+
+/// A parsed filter expression, ready to evaluate against many providers
+/// without re-tokenizing/re-parsing `expr` each time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpr(Expr);
+
+impl FilterExpr {
+    pub fn matches(&self, provider: &ProviderInfo) -> bool {
+        eval(&self.0, provider)
+    }
+}
+
+/// Parse a filter expression without evaluating it.
+pub fn parse(expr: &str) -> Result<FilterExpr> {
+    let tokens = tokenize(expr)?;
+    let mut pos = 0;
+    let parsed = parse_or(&tokens, &mut pos)?;
+    if pos != tokens.len() {
+        return Err(HfpError::Other(format!(
+            "unexpected token after filter expression: {:?}",
+            tokens[pos]
+        )));
+    }
+    Ok(FilterExpr(parsed))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Bool(bool),
+    Num(f64),
+    /// Bareword or quoted string, e.g. `live` in `status = live`.
+    Str(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Cmp { field: String, op: CmpOp, value: Literal },
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Op(CmpOp),
+    Literal(Literal),
+    And,
+    Or,
+    LParen,
+    RParen,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Op(CmpOp::Eq));
+                i += 1;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CmpOp::Ne));
+                i += 2;
+            }
+            '<' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CmpOp::Le));
+                i += 2;
+            }
+            '<' => {
+                tokens.push(Token::Op(CmpOp::Lt));
+                i += 1;
+            }
+            '>' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CmpOp::Ge));
+                i += 2;
+            }
+            '>' => {
+                tokens.push(Token::Op(CmpOp::Gt));
+                i += 1;
+            }
+            '"' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && chars[end] != '"' {
+                    end += 1;
+                }
+                if end >= chars.len() {
+                    return Err(HfpError::Other("unterminated string literal".to_string()));
+                }
+                let s: String = chars[start..end].iter().collect();
+                tokens.push(Token::Literal(Literal::Str(s)));
+                i = end + 1;
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.' || chars[i] == '-')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(classify_word(&word));
+            }
+            other => {
+                return Err(HfpError::Other(format!("unexpected character in filter expression: {other:?}")));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn classify_word(word: &str) -> Token {
+    match word.to_ascii_uppercase().as_str() {
+        "AND" => Token::And,
+        "OR" => Token::Or,
+        "TRUE" => Token::Literal(Literal::Bool(true)),
+        "FALSE" => Token::Literal(Literal::Bool(false)),
+        _ => {
+            if let Ok(n) = word.parse::<f64>() {
+                Token::Literal(Literal::Num(n))
+            } else {
+                Token::Ident(word.to_string())
+            }
+        }
+    }
+}
+
+fn parse_or(tokens: &[Token], pos: &mut usize) -> Result<Expr> {
+    let mut lhs = parse_and(tokens, pos)?;
+    while matches!(tokens.get(*pos), Some(Token::Or)) {
+        *pos += 1;
+        let rhs = parse_and(tokens, pos)?;
+        lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+}
+
+fn parse_and(tokens: &[Token], pos: &mut usize) -> Result<Expr> {
+    let mut lhs = parse_primary(tokens, pos)?;
+    while matches!(tokens.get(*pos), Some(Token::And)) {
+        *pos += 1;
+        let rhs = parse_primary(tokens, pos)?;
+        lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+}
+
+fn parse_primary(tokens: &[Token], pos: &mut usize) -> Result<Expr> {
+    match tokens.get(*pos) {
+        Some(Token::LParen) => {
+            *pos += 1;
+            let inner = parse_or(tokens, pos)?;
+            match tokens.get(*pos) {
+                Some(Token::RParen) => {
+                    *pos += 1;
+                    Ok(inner)
+                }
+                other => Err(HfpError::Other(format!("expected ')' in filter expression, got {other:?}"))),
+            }
+        }
+        Some(Token::Ident(field)) => {
+            let field = field.clone();
+            *pos += 1;
+            let op = match tokens.get(*pos) {
+                Some(Token::Op(op)) => *op,
+                other => return Err(HfpError::Other(format!("expected comparison operator after {field:?}, got {other:?}"))),
+            };
+            *pos += 1;
+            let value = match tokens.get(*pos) {
+                Some(Token::Literal(v)) => v.clone(),
+                Some(Token::Ident(bareword)) => Literal::Str(bareword.clone()),
+                other => return Err(HfpError::Other(format!("expected a value after operator, got {other:?}"))),
+            };
+            *pos += 1;
+            Ok(Expr::Cmp { field, op, value })
+        }
+        other => Err(HfpError::Other(format!("expected a field or '(' in filter expression, got {other:?}"))),
+    }
+}
+
+/// A field's value on a given provider, for comparison against a `Literal`.
+enum FieldVal {
+    Bool(bool),
+    Num(f64),
+    Str(String),
+}
+
+fn field_value(provider: &ProviderInfo, field: &str) -> Option<FieldVal> {
+    match field {
+        "tools" => provider.supports_tools.map(FieldVal::Bool),
+        "structured" => provider.supports_structured.map(FieldVal::Bool),
+        "input_price" => provider.input_price_per_m.map(FieldVal::Num),
+        "output_price" => provider.output_price_per_m.map(FieldVal::Num),
+        "throughput" => provider.throughput_tps.map(FieldVal::Num),
+        "latency" => provider.latency_s.map(FieldVal::Num),
+        "context_window" => provider.context_window.map(|v| FieldVal::Num(v as f64)),
+        "status" => Some(FieldVal::Str(status_str(&provider.status).to_string())),
+        "name" => Some(FieldVal::Str(provider.name.clone())),
+        _ => None,
+    }
+}
+
+fn status_str(status: &ProviderStatus) -> &'static str {
+    match status {
+        ProviderStatus::Live => "live",
+        ProviderStatus::Staging => "staging",
+        ProviderStatus::Unknown => "unknown",
+    }
+}
+
+fn eval_cmp(op: CmpOp, field: &FieldVal, value: &Literal) -> bool {
+    match (field, value) {
+        (FieldVal::Bool(a), Literal::Bool(b)) => match op {
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
+            _ => false,
+        },
+        (FieldVal::Num(a), Literal::Num(b)) => match op {
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
+            CmpOp::Lt => a < b,
+            CmpOp::Le => a <= b,
+            CmpOp::Gt => a > b,
+            CmpOp::Ge => a >= b,
+        },
+        (FieldVal::Str(a), Literal::Str(b)) => match op {
+            CmpOp::Eq => a.eq_ignore_ascii_case(b),
+            CmpOp::Ne => !a.eq_ignore_ascii_case(b),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn eval(expr: &Expr, provider: &ProviderInfo) -> bool {
+    match expr {
+        Expr::Cmp { field, op, value } => match field_value(provider, field) {
+            Some(fv) => eval_cmp(*op, &fv, value),
+            None => false,
+        },
+        Expr::And(a, b) => eval(a, provider) && eval(b, provider),
+        Expr::Or(a, b) => eval(a, provider) || eval(b, provider),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(
+        name: &str,
+        status: ProviderStatus,
+        input_price: Option<f64>,
+        context_window: Option<u64>,
+        tools: Option<bool>,
+    ) -> ProviderInfo {
+        ProviderInfo {
+            name: name.to_string(),
+            status,
+            task: "conversational".to_string(),
+            provider_id: name.to_string(),
+            input_price_per_m: input_price,
+            output_price_per_m: input_price,
+            throughput_tps: None,
+            latency_s: None,
+            context_window,
+            supports_tools: tools,
+            supports_structured: None,
+        }
+    }
+
+    #[test]
+    fn simple_equality() {
+        let f = parse("tools = true").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Live, None, None, Some(true))));
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, None, None, Some(false))));
+    }
+
+    #[test]
+    fn missing_field_never_matches() {
+        let f = parse("tools = true").unwrap();
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, None, None, None)));
+    }
+
+    #[test]
+    fn numeric_comparison() {
+        let f = parse("input_price < 1.0").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Live, Some(0.5), None, None)));
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, Some(2.0), None, None)));
+    }
+
+    #[test]
+    fn and_combinator() {
+        let f = parse("tools = true AND input_price < 1.0").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Live, Some(0.5), None, Some(true))));
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, Some(2.0), None, Some(true))));
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, Some(0.5), None, Some(false))));
+    }
+
+    #[test]
+    fn or_combinator() {
+        let f = parse("status = staging OR status = live").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Live, None, None, None)));
+        assert!(f.matches(&provider("a", ProviderStatus::Staging, None, None, None)));
+        assert!(!f.matches(&provider("a", ProviderStatus::Unknown, None, None, None)));
+    }
+
+    #[test]
+    fn parens_override_precedence() {
+        let f = parse("(status = live OR status = staging) AND tools = true").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Staging, None, None, Some(true))));
+        assert!(!f.matches(&provider("a", ProviderStatus::Staging, None, None, Some(false))));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // Equivalent to `(status = unknown) OR (status = live AND tools = true)`.
+        let f = parse("status = unknown OR status = live AND tools = true").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Unknown, None, None, Some(false))));
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, None, None, Some(false))));
+        assert!(f.matches(&provider("a", ProviderStatus::Live, None, None, Some(true))));
+    }
+
+    #[test]
+    fn context_window_and_status() {
+        let f = parse("context_window >= 32000 AND status = live").unwrap();
+        assert!(f.matches(&provider("a", ProviderStatus::Live, None, Some(64000), None)));
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, None, Some(8000), None)));
+        assert!(!f.matches(&provider("a", ProviderStatus::Staging, None, Some(64000), None)));
+    }
+
+    #[test]
+    fn missing_value_or_leading_keyword_is_a_syntax_error() {
+        assert!(parse("tools =").is_err());
+        assert!(parse("AND tools = true").is_err());
+    }
+
+    #[test]
+    fn unknown_field_never_matches_rather_than_erroring() {
+        let f = parse("made_up_field = true").unwrap();
+        assert!(!f.matches(&provider("a", ProviderStatus::Live, None, None, Some(true))));
+    }
+}
@@ -37,6 +37,10 @@ pub struct GpuSpec {
     pub llamacpp_prefill_eff: f64,
     pub mlx_decode_eff: Option<f64>,
     pub mlx_prefill_eff: Option<f64>,
+    /// System RAM available for CPU-offloaded layers (e.g. llama.cpp `--n-gpu-layers`).
+    pub host_ram_gb: Option<f64>,
+    /// Effective PCIe/host-memory bandwidth for streaming offloaded layers.
+    pub host_bw_gb_s: Option<f64>,
 }
 
 impl GpuSpec {
@@ -91,6 +95,23 @@ pub fn load_bundled_hardware() -> Result<Vec<(String, GpuSpec)>> {
     parse_hardware(toml_str)
 }
 
+/// Load hardware data: cached file if available, otherwise bundled.
+/// Falls back to bundled data if the cached file fails the checksum
+/// recorded in its sidecar metadata (corruption detected).
+#[cfg(feature = "network")]
+pub fn load_hardware_cached() -> Result<Vec<(String, GpuSpec)>> {
+    if let Some(path) = crate::cache::cache_path("hardware.toml") {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            if crate::sync::verify_checksum("hardware.toml", &content) {
+                if let Ok(parsed) = parse_hardware(&content) {
+                    return Ok(parsed);
+                }
+            }
+        }
+    }
+    load_bundled_hardware()
+}
+
 /// Find a GPU by user input like "4090", "rtx4090", "m4-max", "h100".
 /// Normalizes input, then tries exact match, suffix match, substring match.
 /// Also tries matching with underscores stripped so "rtx4090" finds "rtx_4090".
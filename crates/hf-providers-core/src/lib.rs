@@ -3,8 +3,10 @@ pub mod api;
 #[cfg(feature = "network")]
 pub mod cache;
 pub mod cloud;
+pub mod config;
 pub mod error;
 pub mod estimate;
+pub mod filter;
 pub mod hardware;
 pub mod model;
 pub mod pricing;
@@ -16,4 +18,4 @@ pub mod sync;
 
 pub use error::HfpError;
 pub use model::{Model, ModelVariant};
-pub use provider::{Provider, ProviderInfo, ProviderStatus};
+pub use provider::{select_provider, Provider, ProviderInfo, ProviderStatus, SelectCriteria};
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::model::Model;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ProviderStatus {
@@ -78,6 +80,117 @@ pub enum ProviderKind {
     HfInference,
 }
 
+/// Hard requirements and a weighted objective for picking a provider to
+/// serve an assumed workload.
+#[derive(Debug, Clone)]
+pub struct SelectCriteria {
+    pub require_tools: bool,
+    pub require_structured: bool,
+    pub min_context_window: Option<u64>,
+    /// Assumed token mix, used to estimate cost per request.
+    pub input_tokens: f64,
+    pub output_tokens: f64,
+    pub w_cost: f64,
+    pub w_latency: f64,
+    pub w_throughput: f64,
+}
+
+impl Default for SelectCriteria {
+    fn default() -> Self {
+        Self {
+            require_tools: false,
+            require_structured: false,
+            min_context_window: None,
+            input_tokens: 1000.0,
+            output_tokens: 1000.0,
+            w_cost: 1.0,
+            w_latency: 1.0,
+            w_throughput: 1.0,
+        }
+    }
+}
+
+/// Pick the best-scoring `ProviderInfo` for `model` under `criteria`.
+///
+/// Filters to `ProviderStatus::Live` entries meeting the hard requirements,
+/// then scores each candidate as
+/// `w_cost * norm(cost) + w_latency * norm(latency_s) - w_throughput * norm(throughput_tps)`,
+/// where `norm` is min-max normalization over the candidate set and a term
+/// is skipped entirely when every candidate lacks that field. Returns the
+/// lowest-scoring provider, breaking ties by readiness then by name.
+pub fn select_provider<'a>(
+    model: &'a Model,
+    criteria: &SelectCriteria,
+) -> Option<&'a ProviderInfo> {
+    let candidates: Vec<&ProviderInfo> = model
+        .providers
+        .iter()
+        .filter(|p| p.status == ProviderStatus::Live)
+        .filter(|p| !criteria.require_tools || p.supports_tools == Some(true))
+        .filter(|p| !criteria.require_structured || p.supports_structured == Some(true))
+        .filter(|p| match criteria.min_context_window {
+            Some(min) => p.context_window.map(|c| c >= min).unwrap_or(false),
+            None => true,
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let cost = |p: &ProviderInfo| -> Option<f64> {
+        let input = p.input_price_per_m?;
+        let output = p.output_price_per_m?;
+        Some(criteria.input_tokens / 1e6 * input + criteria.output_tokens / 1e6 * output)
+    };
+
+    let costs = normalize(&candidates.iter().map(|p| cost(p)).collect::<Vec<_>>());
+    let latencies = normalize(&candidates.iter().map(|p| p.latency_s).collect::<Vec<_>>());
+    let throughputs = normalize(&candidates.iter().map(|p| p.throughput_tps).collect::<Vec<_>>());
+
+    let mut scored: Vec<(usize, f64)> = (0..candidates.len())
+        .map(|i| {
+            let mut score = 0.0;
+            if let Some(c) = costs[i] {
+                score += criteria.w_cost * c;
+            }
+            if let Some(l) = latencies[i] {
+                score += criteria.w_latency * l;
+            }
+            if let Some(t) = throughputs[i] {
+                score -= criteria.w_throughput * t;
+            }
+            (i, score)
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| candidates[a.0].readiness().cmp(&candidates[b.0].readiness()))
+            .then_with(|| candidates[a.0].name.cmp(&candidates[b.0].name))
+    });
+
+    scored.first().map(|&(i, _)| candidates[i])
+}
+
+/// Min-max normalize optional values to `[0, 1]`. A field missing from every
+/// candidate yields `None` for all entries (so the caller skips that term);
+/// a field present but constant across candidates yields `0.0`.
+fn normalize(values: &[Option<f64>]) -> Vec<Option<f64>> {
+    let present: Vec<f64> = values.iter().filter_map(|v| *v).collect();
+    if present.is_empty() {
+        return vec![None; values.len()];
+    }
+    let min = present.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = present.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .iter()
+        .map(|v| v.map(|x| if range > 0.0 { (x - min) / range } else { 0.0 }))
+        .collect()
+}
+
 pub const PROVIDERS: &[Provider] = &[
     Provider { id: "cerebras",       display_name: "Cerebras",     kind: ProviderKind::InferenceProvider },
     Provider { id: "cohere",         display_name: "Cohere",       kind: ProviderKind::InferenceProvider },
@@ -5,11 +5,17 @@ use crate::model::Model;
 #[cfg(feature = "network")]
 use crate::provider::ProviderInfo;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Lang {
     Python,
     Curl,
     Javascript,
+    Typescript,
+    Go,
+    /// OpenAI Python SDK pointed at the HF router's OpenAI-compatible endpoint.
+    OpenAIPython,
+    /// OpenAI JavaScript SDK pointed at the HF router's OpenAI-compatible endpoint.
+    OpenAIJavascript,
 }
 
 impl FromStr for Lang {
@@ -19,14 +25,52 @@ impl FromStr for Lang {
             "python" | "py" => Ok(Self::Python),
             "curl" => Ok(Self::Curl),
             "js" | "javascript" => Ok(Self::Javascript),
+            "ts" | "typescript" => Ok(Self::Typescript),
+            "go" | "golang" => Ok(Self::Go),
+            "openai-python" | "openai_python" => Ok(Self::OpenAIPython),
+            "openai-js" | "openai-javascript" | "openai_javascript" => Ok(Self::OpenAIJavascript),
             other => Err(format!("unknown lang: {other}")),
         }
     }
 }
 
+/// Endpoint and auth overrides for generated snippets, so they can target a
+/// self-hosted gateway or corporate proxy instead of the default HF router.
+/// `base_url` is used by the curl, Go and OpenAI SDK snippets only; the
+/// `InferenceClient` snippets (Python, Javascript, Typescript) do not use it,
+/// and the Python one does not use `token_env` either.
+#[derive(Debug, Clone)]
+pub struct SnippetConfig {
+    /// Defaults to `https://router.huggingface.co`.
+    pub base_url: String,
+    /// Name of the env var holding the token. Defaults to `HF_TOKEN`.
+    pub token_env: String,
+}
+
+impl Default for SnippetConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://router.huggingface.co".to_string(),
+            token_env: "HF_TOKEN".to_string(),
+        }
+    }
+}
+
 /// Generate a snippet from just model_id and provider name strings.
 pub fn generate_simple(model_id: &str, provider_name: &str, lang: Lang) -> String {
+    generate_simple_with_config(model_id, provider_name, lang, &SnippetConfig::default())
+}
+
+/// Like `generate_simple`, but targeting a custom `base_url`/token env var.
+pub fn generate_simple_with_config(
+    model_id: &str,
+    provider_name: &str,
+    lang: Lang,
+    config: &SnippetConfig,
+) -> String {
     let prov = provider_name;
+    let base_url = &config.base_url;
+    let token_env = &config.token_env;
 
     match lang {
         Lang::Python => format!(
@@ -41,8 +85,8 @@ print(response.choices[0].message.content)"#
         ),
 
         Lang::Curl => format!(
-            r#"curl -X POST https://router.huggingface.co/v1/chat/completions \
-  -H "Authorization: Bearer $HF_TOKEN" \
+            r#"curl -X POST {base_url}/v1/chat/completions \
+  -H "Authorization: Bearer ${token_env}" \
   -H "Content-Type: application/json" \
   -d '{{"model":"{model_id}:{prov}","messages":[{{"role":"user","content":"Hello!"}}]}}'"#
         ),
@@ -50,7 +94,7 @@ print(response.choices[0].message.content)"#
         Lang::Javascript => format!(
             r#"import {{ InferenceClient }} from "@huggingface/inference";
 
-const client = new InferenceClient(process.env.HF_TOKEN);
+const client = new InferenceClient(process.env.{token_env});
 const result = await client.chatCompletion({{
   model: "{model_id}",
   provider: "{prov}",
@@ -58,10 +102,269 @@ const result = await client.chatCompletion({{
 }});
 console.log(result.choices[0].message.content);"#
         ),
+
+        Lang::Typescript => format!(
+            r#"import {{ InferenceClient, type ChatCompletionInputMessage }} from "@huggingface/inference";
+
+const client = new InferenceClient(process.env.{token_env});
+const messages: ChatCompletionInputMessage[] = [{{ role: "user", content: "Hello!" }}];
+const result = await client.chatCompletion({{
+  model: "{model_id}",
+  provider: "{prov}",
+  messages,
+}});
+console.log(result.choices[0].message.content);"#
+        ),
+
+        Lang::Go => format!(
+            r#"package main
+
+import (
+	"bytes"
+	"fmt"
+	"net/http"
+	"os"
+)
+
+func main() {{
+	body := []byte(`{{"model":"{model_id}:{prov}","messages":[{{"role":"user","content":"Hello!"}}]}}`)
+	req, _ := http.NewRequest("POST", "{base_url}/v1/chat/completions", bytes.NewBuffer(body))
+	req.Header.Set("Authorization", "Bearer "+os.Getenv("{token_env}"))
+	req.Header.Set("Content-Type", "application/json")
+
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {{
+		panic(err)
+	}}
+	defer resp.Body.Close()
+	fmt.Println(resp.Status)
+}}"#
+        ),
+
+        // `model_id` here is the provider-specific model string (see `generate`,
+        // which passes `provider.provider_id` for these two variants).
+        Lang::OpenAIPython => format!(
+            r#"import os
+from openai import OpenAI
+
+client = OpenAI(
+    base_url="{base_url}/{prov}/v1",
+    api_key=os.environ["{token_env}"],
+)
+response = client.chat.completions.create(
+    model="{model_id}",
+    messages=[{{"role": "user", "content": "Hello!"}}]
+)
+print(response.choices[0].message.content)"#
+        ),
+
+        Lang::OpenAIJavascript => format!(
+            r#"import OpenAI from "openai";
+
+const client = new OpenAI({{
+  baseURL: "{base_url}/{prov}/v1",
+  apiKey: process.env.{token_env},
+}});
+const response = await client.chat.completions.create({{
+  model: "{model_id}",
+  messages: [{{ role: "user", content: "Hello!" }}],
+}});
+console.log(response.choices[0].message.content);"#
+        ),
+    }
+}
+
+/// Comment prefix for a throwaway note in each language's syntax.
+fn comment_prefix(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Python | Lang::Curl => "#",
+        _ => "//",
+    }
+}
+
+/// A runnable tool-calling round trip for the Python, Javascript and curl
+/// targets, appended when the provider advertises `supports_tools`; other
+/// languages get nothing appended in that case. `Some(false)` appends a
+/// commented note in every language, and `None` (unknown) emits nothing,
+/// matching the plain snippet.
+fn tools_section(
+    lang: Lang,
+    model_id: &str,
+    prov: &str,
+    supports_tools: Option<bool>,
+    config: &SnippetConfig,
+) -> String {
+    let base_url = &config.base_url;
+    let token_env = &config.token_env;
+    match (lang, supports_tools) {
+        (_, None) => String::new(),
+        (_, Some(false)) => format!(
+            "\n\n{} Note: {prov} does not advertise tool-calling support for this model.",
+            comment_prefix(lang)
+        ),
+        (Lang::Python, Some(true)) => format!(
+            r#"
+
+# Tool calling example
+tools = [
+    {{
+        "type": "function",
+        "function": {{
+            "name": "get_weather",
+            "description": "Get the current weather for a location",
+            "parameters": {{
+                "type": "object",
+                "properties": {{"location": {{"type": "string"}}}},
+                "required": ["location"],
+            }},
+        }},
+    }}
+]
+tool_response = client.chat.completions.create(
+    model="{model_id}",
+    messages=[{{"role": "user", "content": "What's the weather in Paris?"}}],
+    tools=tools,
+)
+print(tool_response.choices[0].message.tool_calls)"#
+        ),
+        (Lang::Javascript, Some(true)) => format!(
+            r#"
+
+// Tool calling example
+const tools = [
+  {{
+    type: "function",
+    function: {{
+      name: "get_weather",
+      description: "Get the current weather for a location",
+      parameters: {{
+        type: "object",
+        properties: {{ location: {{ type: "string" }} }},
+        required: ["location"],
+      }},
+    }},
+  }},
+];
+const toolResult = await client.chatCompletion({{
+  model: "{model_id}",
+  provider: "{prov}",
+  messages: [{{ role: "user", content: "What's the weather in Paris?" }}],
+  tools,
+}});
+console.log(toolResult.choices[0].message.tool_calls);"#
+        ),
+        (Lang::Curl, Some(true)) => format!(
+            r#"
+
+# Tool calling example
+curl -X POST {base_url}/v1/chat/completions \
+  -H "Authorization: Bearer ${token_env}" \
+  -H "Content-Type: application/json" \
+  -d '{{"model":"{model_id}:{prov}","messages":[{{"role":"user","content":"What'"'"'s the weather in Paris?"}}],"tools":[{{"type":"function","function":{{"name":"get_weather","description":"Get the current weather for a location","parameters":{{"type":"object","properties":{{"location":{{"type":"string"}}}},"required":["location"]}}}}}}]}}'"#
+        ),
+        _ => String::new(),
+    }
+}
+
+/// A runnable structured-output example with a sample JSON schema for the
+/// Python, Javascript and curl targets, appended when the provider advertises
+/// `supports_structured`; other languages get nothing appended in that case.
+/// `Some(false)` appends a commented note in every language, and `None`
+/// (unknown) emits nothing.
+fn structured_section(
+    lang: Lang,
+    model_id: &str,
+    prov: &str,
+    supports_structured: Option<bool>,
+    config: &SnippetConfig,
+) -> String {
+    let base_url = &config.base_url;
+    let token_env = &config.token_env;
+    match (lang, supports_structured) {
+        (_, None) => String::new(),
+        (_, Some(false)) => format!(
+            "\n\n{} Note: {prov} does not advertise structured-output support for this model.",
+            comment_prefix(lang)
+        ),
+        (Lang::Python, Some(true)) => format!(
+            r#"
+
+# Structured output example
+response_format = {{
+    "type": "json_schema",
+    "json_schema": {{
+        "name": "answer",
+        "schema": {{
+            "type": "object",
+            "properties": {{"answer": {{"type": "string"}}}},
+            "required": ["answer"],
+        }},
+    }},
+}}
+structured_response = client.chat.completions.create(
+    model="{model_id}",
+    messages=[{{"role": "user", "content": "Reply with a JSON object."}}],
+    response_format=response_format,
+)
+print(structured_response.choices[0].message.content)"#
+        ),
+        (Lang::Javascript, Some(true)) => format!(
+            r#"
+
+// Structured output example
+const responseFormat = {{
+  type: "json_schema",
+  json_schema: {{
+    name: "answer",
+    schema: {{
+      type: "object",
+      properties: {{ answer: {{ type: "string" }} }},
+      required: ["answer"],
+    }},
+  }},
+}};
+const structuredResult = await client.chatCompletion({{
+  model: "{model_id}",
+  provider: "{prov}",
+  messages: [{{ role: "user", content: "Reply with a JSON object." }}],
+  response_format: responseFormat,
+}});
+console.log(structuredResult.choices[0].message.content);"#
+        ),
+        (Lang::Curl, Some(true)) => format!(
+            r#"
+
+# Structured output example
+curl -X POST {base_url}/v1/chat/completions \
+  -H "Authorization: Bearer ${token_env}" \
+  -H "Content-Type: application/json" \
+  -d '{{"model":"{model_id}:{prov}","messages":[{{"role":"user","content":"Reply with a JSON object."}}],"response_format":{{"type":"json_schema","json_schema":{{"name":"answer","schema":{{"type":"object","properties":{{"answer":{{"type":"string"}}}},"required":["answer"]}}}}}}}}'"#
+        ),
+        _ => String::new(),
     }
 }
 
 #[cfg(feature = "network")]
 pub fn generate(model: &Model, provider: &ProviderInfo, lang: Lang) -> String {
-    generate_simple(&model.id, &provider.name, lang)
+    generate_with_config(model, provider, lang, &SnippetConfig::default())
+}
+
+/// Like `generate`, but targeting a custom `base_url`/token env var.
+#[cfg(feature = "network")]
+pub fn generate_with_config(
+    model: &Model,
+    provider: &ProviderInfo,
+    lang: Lang,
+    config: &SnippetConfig,
+) -> String {
+    let (model_id, prov): (&str, &str) = match lang {
+        // The OpenAI-compatible router addresses models by the provider's own
+        // model string, not the HF model id.
+        Lang::OpenAIPython | Lang::OpenAIJavascript => (&provider.provider_id, &provider.name),
+        _ => (&model.id, &provider.name),
+    };
+
+    let base = generate_simple_with_config(model_id, prov, lang, config);
+    base + &tools_section(lang, model_id, prov, provider.supports_tools, config)
+        + &structured_section(lang, model_id, prov, provider.supports_structured, config)
 }
@@ -1,56 +1,469 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::api::{backoff_delay, RetryPolicy};
 use crate::cache;
 use crate::cloud;
 use crate::error::{HfpError, Result};
 use crate::hardware;
 
-const HARDWARE_URL: &str =
-    "https://raw.githubusercontent.com/jadnohra/hf-providers/main/data/hardware.toml";
-const CLOUD_URL: &str =
-    "https://raw.githubusercontent.com/jadnohra/hf-providers/main/data/cloud.toml";
+/// Default base location: this project's own GitHub repo, raw-served.
+const DEFAULT_BASE_URL: &str = "https://raw.githubusercontent.com/jadnohra/hf-providers/main/data";
+
+/// Sidecar validators for a cached data file, used to make the next sync a
+/// conditional request (`If-None-Match` / `If-Modified-Since`), when it was
+/// last actually fetched (for staleness checks that don't depend on
+/// filesystem mtimes surviving a backup/restore or `cp -p`), and its content
+/// checksum (for detecting corruption on load).
+#[derive(Serialize, Deserialize, Default, Clone)]
+struct FileMeta {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: Option<u64>,
+    sha256: Option<String>,
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Hex-encoded SHA-256 of `data`.
+fn sha256_hex(data: &[u8]) -> String {
+    let digest = Sha256::digest(data);
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Pinned checksums a publisher can supply so [`sync_data_full`] rejects a
+/// tampered or truncated download before it ever reaches the cache, keyed by
+/// filename (e.g. `"hardware.toml"`).
+pub type ChecksumManifest = BTreeMap<String, String>;
+
+/// Verify `content` against the SHA-256 checksum recorded the last time
+/// `filename` was synced. Returns `true` if there's no recorded checksum
+/// (a cache from before this existed, or data that was never synced) — that
+/// absence isn't evidence of corruption, just nothing to check against.
+pub fn verify_checksum(filename: &str, content: &str) -> bool {
+    let Some(cache_dir) = cache::cache_dir() else { return true };
+    verify_checksum_in_dir(&cache_dir, filename, content)
+}
+
+/// Whether `content` matches the checksum recorded for `filename`'s sidecar
+/// metadata in `cache_dir`, if one was recorded at all.
+fn verify_checksum_in_dir(cache_dir: &Path, filename: &str, content: &str) -> bool {
+    match read_meta(cache_dir, filename).sha256 {
+        Some(expected) => sha256_hex(content.as_bytes()) == expected,
+        None => true,
+    }
+}
+
+/// Whether a conditional sync actually re-downloaded a file.
+pub enum FileStatus {
+    Updated(usize),
+    Unchanged,
+    /// The host couldn't be reached after exhausting the retry policy; the
+    /// existing cached/bundled copy should keep being used.
+    Offline,
+}
+
+/// A single file's sync outcome plus how many attempts it took, for
+/// diagnostics.
+pub struct FileSync {
+    pub status: FileStatus,
+    pub attempts: u32,
+}
 
 pub struct SyncResult {
-    pub hardware_count: usize,
-    pub cloud_count: usize,
+    pub hardware: FileSync,
+    pub cloud: FileSync,
 }
 
-/// Download hardware.toml and cloud.toml from GitHub, validate, and write to cache.
-pub async fn sync_data() -> Result<SyncResult> {
-    let client = reqwest::Client::new();
-    let cache_dir =
-        cache::cache_dir().ok_or_else(|| HfpError::Io("cannot determine cache directory".into()))?;
+/// Outcome of a TTL-aware sync: either the cache was already fresh and
+/// nothing was downloaded, or a real sync happened.
+pub enum SyncOutcome {
+    Fresh { age: Duration },
+    Synced(SyncResult),
+}
 
-    // Download both in parallel.
-    let (hw_resp, cl_resp) = tokio::join!(
-        client.get(HARDWARE_URL).send(),
-        client.get(CLOUD_URL).send(),
-    );
+/// Age of one cached file: the recorded `fetched_at` from its sidecar
+/// metadata if present, else the file's own mtime (for caches written before
+/// `fetched_at` existed). `None` if the file is missing.
+fn file_age(cache_dir: &Path, filename: &str) -> Option<Duration> {
+    let path = cache_dir.join(filename);
+    if !path.exists() {
+        return None;
+    }
+    if let Some(fetched_at) = read_meta(cache_dir, filename).fetched_at {
+        return Some(Duration::from_secs(unix_now().saturating_sub(fetched_at)));
+    }
+    std::fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()
+}
 
-    let hw_text = hw_resp
-        .map_err(|e| HfpError::Io(format!("failed to download hardware.toml: {e}")))?
-        .text()
-        .await
-        .map_err(|e| HfpError::Io(format!("failed to read hardware.toml response: {e}")))?;
+/// Age of the cached data files, i.e. how long ago the older of
+/// hardware.toml/cloud.toml was last fetched. `None` if either file is
+/// missing, in which case the cache should be treated as stale.
+pub fn cache_age() -> Option<Duration> {
+    let cache_dir = cache::cache_dir()?;
+    let hw_age = file_age(&cache_dir, "hardware.toml")?;
+    let cl_age = file_age(&cache_dir, "cloud.toml")?;
+    Some(hw_age.max(cl_age))
+}
 
-    let cl_text = cl_resp
-        .map_err(|e| HfpError::Io(format!("failed to download cloud.toml: {e}")))?
-        .text()
-        .await
-        .map_err(|e| HfpError::Io(format!("failed to read cloud.toml response: {e}")))?;
+/// Sync unless the cache is younger than `ttl`, in which case this is a
+/// no-op that reports the cache's age. `force` always re-downloads.
+/// `base_url` overrides the default GitHub location, see [`sync_data_from`].
+pub async fn sync_if_stale(ttl: Duration, force: bool, base_url: Option<&str>) -> Result<SyncOutcome> {
+    if !force {
+        if let Some(age) = cache_age() {
+            if age < ttl {
+                return Ok(SyncOutcome::Fresh { age });
+            }
+        }
+    }
+    Ok(SyncOutcome::Synced(sync_data_from(base_url).await?))
+}
 
-    // Validate by parsing before writing.
-    let hw = hardware::parse_hardware(&hw_text)?;
-    let cl = cloud::parse_cloud(&cl_text)?;
+/// Offline-first loading: if the cache is older than `max_age` (or missing),
+/// kick off a [`sync_data_from`] on a background task and return immediately
+/// without waiting for it. Callers keep reading via
+/// `hardware::load_hardware_cached`/`cloud::load_cloud_cached`, which serve
+/// whatever's on disk (or the bundled data) right away — this only makes
+/// sure a refresh is in flight so the *next* read is current. Must be called
+/// from within a Tokio runtime.
+pub fn refresh_in_background_if_stale(max_age: Duration, base_url: Option<&str>) {
+    let stale = cache_age().map(|age| age >= max_age).unwrap_or(true);
+    if !stale {
+        return;
+    }
+    let base_url = base_url.map(str::to_string);
+    tokio::spawn(async move {
+        let _ = sync_data_from(base_url.as_deref()).await;
+    });
+}
+
+/// Outcome of asking a [`SyncSource`] for one file.
+enum FetchOutcome {
+    Unchanged,
+    Fetched { text: String, meta: FileMeta },
+}
+
+/// Failure from a [`SyncSource`] fetch, split so the retry loop knows what's
+/// safe to retry: `Unreachable` means the host never responded (DNS/connect/
+/// timeout) and gets retried with backoff, while `Other` means a response
+/// did arrive but signals failure, or the body was unreadable — retrying
+/// that wouldn't help, so it fails the sync immediately.
+enum FetchError {
+    Unreachable(String),
+    Other(HfpError),
+}
+
+/// A backend `hf-providers` can sync `hardware.toml`/`cloud.toml` from. Picked
+/// by [`resolve_source`] based on the scheme of the configured base location.
+#[async_trait]
+trait SyncSource: Send + Sync {
+    /// Fetch `filename`, using `prior` (the sidecar validators from the last
+    /// successful fetch) to make the request conditional where the backend
+    /// supports it. Returns [`FetchOutcome::Unchanged`] if the backend can
+    /// tell the file hasn't changed without re-downloading it.
+    async fn fetch(
+        &self,
+        filename: &str,
+        prior: &FileMeta,
+    ) -> std::result::Result<FetchOutcome, FetchError>;
+}
 
-    // Write to cache.
-    std::fs::write(cache_dir.join("hardware.toml"), &hw_text)
-        .map_err(|e| HfpError::Io(format!("failed to write hardware.toml cache: {e}")))?;
-    std::fs::write(cache_dir.join("cloud.toml"), &cl_text)
-        .map_err(|e| HfpError::Io(format!("failed to write cloud.toml cache: {e}")))?;
+/// Plain HTTP(S) source, used for the default GitHub raw location and any
+/// other `https://`/`http://` base URL. Makes `ETag`/`Last-Modified`
+/// conditional requests.
+struct HttpSource {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpSource {
+    fn new(base_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
 
-    Ok(SyncResult {
-        hardware_count: hw.len(),
-        cloud_count: cl.len(),
-    })
+#[async_trait]
+impl SyncSource for HttpSource {
+    async fn fetch(
+        &self,
+        filename: &str,
+        prior: &FileMeta,
+    ) -> std::result::Result<FetchOutcome, FetchError> {
+        let url = format!("{}/{filename}", self.base_url);
+        let mut req = self.client.get(&url);
+        if let Some(etag) = &prior.etag {
+            req = req.header(IF_NONE_MATCH, etag.clone());
+        } else if let Some(last_modified) = &prior.last_modified {
+            req = req.header(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+
+        let resp = req.send().await.map_err(|e| {
+            if e.is_connect() || e.is_timeout() {
+                FetchError::Unreachable(format!("failed to reach host for {filename}: {e}"))
+            } else {
+                FetchError::Other(HfpError::Io(format!("failed to download {filename}: {e}")))
+            }
+        })?;
+
+        if resp.status().as_u16() == 304 {
+            return Ok(FetchOutcome::Unchanged);
+        }
+        if !resp.status().is_success() {
+            return Err(FetchError::Other(HfpError::Io(format!(
+                "failed to download {filename}: HTTP {}",
+                resp.status()
+            ))));
+        }
+
+        let meta = FileMeta {
+            etag: resp.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(String::from),
+            last_modified: resp
+                .headers()
+                .get(LAST_MODIFIED)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from),
+            fetched_at: None,
+            sha256: None,
+        };
+        let text = resp.text().await.map_err(|e| {
+            FetchError::Other(HfpError::Io(format!("failed to read {filename} response: {e}")))
+        })?;
+        Ok(FetchOutcome::Fetched { text, meta })
+    }
+}
+
+/// Object-store-backed source for `s3://`, `gs://`, and `az://` base URLs,
+/// letting an organization host its own copies of the data files in a
+/// private bucket. `object_store` abstracts AWS S3, Google Cloud Storage, and
+/// Azure Blob behind one interface, including credential discovery from the
+/// environment (e.g. `AWS_ACCESS_KEY_ID`).
+///
+/// Unlike [`HttpSource`], object stores don't expose a uniform conditional-GET
+/// primitive, so every fetch re-downloads the object; the returned `ETag` is
+/// still recorded for diagnostics.
+struct ObjectStoreSource {
+    store: Box<dyn object_store::ObjectStore>,
+    prefix: object_store::path::Path,
+}
+
+impl ObjectStoreSource {
+    fn new(base_url: &str) -> Result<Self> {
+        let url = url::Url::parse(base_url)
+            .map_err(|e| HfpError::Io(format!("bad sync base_url {base_url}: {e}")))?;
+        let (store, prefix) = object_store::parse_url(&url)
+            .map_err(|e| HfpError::Io(format!("bad sync base_url {base_url}: {e}")))?;
+        Ok(Self { store, prefix })
+    }
+}
+
+#[async_trait]
+impl SyncSource for ObjectStoreSource {
+    async fn fetch(
+        &self,
+        filename: &str,
+        _prior: &FileMeta,
+    ) -> std::result::Result<FetchOutcome, FetchError> {
+        let path = self.prefix.child(filename);
+        let result = self
+            .store
+            .get(&path)
+            .await
+            .map_err(|e| classify_object_store_error(filename, &e))?;
+        let etag = result.meta.e_tag.clone();
+        let bytes = result.bytes().await.map_err(|e| classify_object_store_error(filename, &e))?;
+        let text = String::from_utf8(bytes.to_vec()).map_err(|e| {
+            FetchError::Other(HfpError::Io(format!("{filename} is not valid UTF-8: {e}")))
+        })?;
+        Ok(FetchOutcome::Fetched {
+            text,
+            meta: FileMeta { etag, last_modified: None, fetched_at: None, sha256: None },
+        })
+    }
+}
+
+/// `object_store` doesn't expose a typed "couldn't reach the backend"
+/// variant the way `reqwest::Error::is_connect`/`is_timeout` do, so this
+/// falls back to sniffing the error message for connectivity wording.
+fn classify_object_store_error(filename: &str, e: &object_store::Error) -> FetchError {
+    let msg = e.to_string();
+    let lower = msg.to_lowercase();
+    if lower.contains("timed out") || lower.contains("timeout") || lower.contains("connect") || lower.contains("dns")
+    {
+        FetchError::Unreachable(format!("failed to reach object store for {filename}: {msg}"))
+    } else {
+        FetchError::Other(HfpError::Io(format!("failed to download {filename}: {msg}")))
+    }
+}
+
+/// Pick a [`SyncSource`] for `base_url` by scheme: `https://`/`http://` use a
+/// plain conditional GET, while `s3://`, `gs://`, and `az://` route through
+/// `object_store`.
+fn resolve_source(base_url: &str) -> Result<Box<dyn SyncSource>> {
+    if base_url.starts_with("s3://") || base_url.starts_with("gs://") || base_url.starts_with("az://") {
+        Ok(Box::new(ObjectStoreSource::new(base_url)?))
+    } else {
+        Ok(Box::new(HttpSource::new(base_url)))
+    }
+}
+
+fn meta_path(cache_dir: &Path, filename: &str) -> std::path::PathBuf {
+    cache_dir.join(format!("{filename}.meta"))
+}
+
+fn read_meta(cache_dir: &Path, filename: &str) -> FileMeta {
+    std::fs::read_to_string(meta_path(cache_dir, filename))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn write_meta(cache_dir: &Path, filename: &str, meta: &FileMeta) {
+    if let Ok(json) = serde_json::to_string(meta) {
+        let _ = std::fs::write(meta_path(cache_dir, filename), json);
+    }
+}
+
+/// Write `text` into the cache atomically: write to a pid-suffixed `.tmp`
+/// sibling, then `rename` it over the real path, so a crash or kill mid-write
+/// can never leave a half-written `hardware.toml`/`cloud.toml` behind. The
+/// pid suffix also keeps two concurrent syncers (e.g. a background refresh
+/// racing a foreground `sync --force`) from clobbering each other's temp file.
+fn write_cache_file_atomically(cache_dir: &Path, filename: &str, text: &str) -> Result<()> {
+    let tmp_path = cache_dir.join(format!("{filename}.{}.tmp", std::process::id()));
+    std::fs::write(&tmp_path, text)
+        .map_err(|e| HfpError::Io(format!("failed to write {filename} cache: {e}")))?;
+    std::fs::rename(&tmp_path, cache_dir.join(filename))
+        .map_err(|e| HfpError::Io(format!("failed to install {filename} cache: {e}")))?;
+    Ok(())
+}
+
+/// Fetch `filename` from `source`, retrying `Unreachable` failures (host
+/// never responded) up to `retry.max_attempts` with exponential backoff.
+/// A response that did arrive but signals an error, a checksum mismatch
+/// against `expected_checksum`, or a downloaded body that fails `validate`,
+/// is never retried and fails the sync immediately. If every attempt is
+/// `Unreachable`, this reports [`FileStatus::Offline`] rather than an error,
+/// so callers can keep using the cached/bundled data.
+async fn fetch_and_validate(
+    source: &dyn SyncSource,
+    cache_dir: &Path,
+    filename: &str,
+    retry: RetryPolicy,
+    expected_checksum: Option<&str>,
+    validate: impl Fn(&str) -> Result<usize>,
+) -> Result<FileSync> {
+    let prior = read_meta(cache_dir, filename);
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match source.fetch(filename, &prior).await {
+            Ok(FetchOutcome::Unchanged) => {
+                // The server confirmed this is still current, so the
+                // staleness clock resets even though nothing was downloaded.
+                let mut meta = prior.clone();
+                meta.fetched_at = Some(unix_now());
+                write_meta(cache_dir, filename, &meta);
+                return Ok(FileSync { status: FileStatus::Unchanged, attempts: attempt });
+            }
+            Ok(FetchOutcome::Fetched { text, mut meta }) => {
+                let sha256 = sha256_hex(text.as_bytes());
+                if let Some(expected) = expected_checksum {
+                    if expected != sha256 {
+                        return Err(HfpError::Io(format!(
+                            "checksum mismatch for {filename}: expected {expected}, got {sha256}"
+                        )));
+                    }
+                }
+                let count = validate(&text)?;
+                write_cache_file_atomically(cache_dir, filename, &text)?;
+                meta.fetched_at = Some(unix_now());
+                meta.sha256 = Some(sha256);
+                write_meta(cache_dir, filename, &meta);
+                return Ok(FileSync { status: FileStatus::Updated(count), attempts: attempt });
+            }
+            Err(FetchError::Unreachable(_)) if attempt < retry.max_attempts => {
+                tokio::time::sleep(backoff_delay(&retry, attempt)).await;
+            }
+            Err(FetchError::Unreachable(_)) => {
+                return Ok(FileSync { status: FileStatus::Offline, attempts: attempt })
+            }
+            Err(FetchError::Other(e)) => return Err(e),
+        }
+    }
+}
+
+/// Download hardware.toml and cloud.toml from the default GitHub location,
+/// skipping either one the server reports as unchanged (via
+/// `ETag`/`Last-Modified`). Only a fresh download is validated, written to
+/// the cache, and counted.
+pub async fn sync_data() -> Result<SyncResult> {
+    sync_data_from(None).await
+}
+
+/// Like [`sync_data`], but fetches from `base_url` instead of the default
+/// GitHub location. `base_url` may be `https://`/`http://` (plain conditional
+/// GET) or `s3://`/`gs://`/`az://` (routed through `object_store`), letting an
+/// organization host its own copies of `hardware.toml`/`cloud.toml`.
+pub async fn sync_data_from(base_url: Option<&str>) -> Result<SyncResult> {
+    sync_data_with(base_url, RetryPolicy::default()).await
+}
+
+/// Like [`sync_data_from`], with an explicit retry policy for transient
+/// connectivity failures instead of [`RetryPolicy::default`].
+pub async fn sync_data_with(base_url: Option<&str>, retry: RetryPolicy) -> Result<SyncResult> {
+    sync_data_full(base_url, retry, None).await
+}
+
+/// Like [`sync_data_with`], additionally pinning each file against an
+/// expected SHA-256 from `expected_checksums` (keyed by filename) when
+/// given, so a publisher can reject a tampered or partial download before it
+/// ever reaches the cache.
+pub async fn sync_data_full(
+    base_url: Option<&str>,
+    retry: RetryPolicy,
+    expected_checksums: Option<&ChecksumManifest>,
+) -> Result<SyncResult> {
+    let source = resolve_source(base_url.unwrap_or(DEFAULT_BASE_URL))?;
+    let cache_dir =
+        cache::cache_dir().ok_or_else(|| HfpError::Io("cannot determine cache directory".into()))?;
+    let expected = |filename: &str| expected_checksums.and_then(|m| m.get(filename)).map(String::as_str);
+
+    // Fetch both in parallel.
+    let (hardware, cloud) = tokio::join!(
+        fetch_and_validate(
+            source.as_ref(),
+            &cache_dir,
+            "hardware.toml",
+            retry,
+            expected("hardware.toml"),
+            |s| hardware::parse_hardware(s).map(|v| v.len())
+        ),
+        fetch_and_validate(
+            source.as_ref(),
+            &cache_dir,
+            "cloud.toml",
+            retry,
+            expected("cloud.toml"),
+            |s| cloud::parse_cloud(s).map(|v| v.len())
+        ),
+    );
+
+    Ok(SyncResult { hardware: hardware?, cloud: cloud? })
 }
 
 #[cfg(test)]
@@ -63,16 +476,14 @@ mod tests {
     #[ignore]
     async fn sync_downloads_and_validates() {
         let result = sync_data().await.expect("sync should succeed");
-        assert!(
-            result.hardware_count >= 200,
-            "expected >=200 GPUs, got {}",
-            result.hardware_count
-        );
-        assert!(
-            result.cloud_count >= 10,
-            "expected >=10 cloud offerings, got {}",
-            result.cloud_count
-        );
+        match result.hardware.status {
+            FileStatus::Updated(count) => assert!(count >= 200, "expected >=200 GPUs, got {count}"),
+            FileStatus::Unchanged | FileStatus::Offline => {}
+        }
+        match result.cloud.status {
+            FileStatus::Updated(count) => assert!(count >= 10, "expected >=10 cloud offerings, got {count}"),
+            FileStatus::Unchanged | FileStatus::Offline => {}
+        }
 
         // Verify files were written to cache.
         let hw_path = cache::cache_path("hardware.toml").expect("cache path");
@@ -89,4 +500,154 @@ mod tests {
         let offerings = cloud::load_cloud_cached().expect("should load cloud");
         assert!(offerings.len() >= 10);
     }
+
+    #[test]
+    fn resolve_source_picks_backend_by_scheme() {
+        assert!(resolve_source(DEFAULT_BASE_URL).is_ok());
+        assert!(resolve_source("http://internal.example/data").is_ok());
+        assert!(resolve_source("s3://my-bucket/hf-providers-data").is_ok());
+        assert!(resolve_source("gs://my-bucket/hf-providers-data").is_ok());
+        assert!(resolve_source("az://my-container/hf-providers-data").is_ok());
+    }
+
+    /// A [`SyncSource`] that reports `Unreachable` for its first `fail_times`
+    /// calls, then succeeds — used to exercise the retry loop without a
+    /// network.
+    struct FlakySource {
+        attempts: std::sync::atomic::AtomicU32,
+        fail_times: u32,
+    }
+
+    #[async_trait]
+    impl SyncSource for FlakySource {
+        async fn fetch(
+            &self,
+            filename: &str,
+            _prior: &FileMeta,
+        ) -> std::result::Result<FetchOutcome, FetchError> {
+            let n = self.attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if n <= self.fail_times {
+                return Err(FetchError::Unreachable(format!("{filename} unreachable (attempt {n})")));
+            }
+            Ok(FetchOutcome::Fetched { text: "ok".to_string(), meta: FileMeta::default() })
+        }
+    }
+
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("hfp-sync-test-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create scratch dir");
+        dir
+    }
+
+    #[tokio::test]
+    async fn fetch_and_validate_retries_then_recovers() {
+        let source = FlakySource { attempts: Default::default(), fail_times: 2 };
+        let retry = RetryPolicy { max_attempts: 5, base_delay: Duration::from_millis(1) };
+        let result = fetch_and_validate(
+            &source,
+            &scratch_dir("recovers"),
+            "test.toml",
+            retry,
+            None,
+            |s| Ok(s.len()),
+        )
+        .await
+        .expect("should eventually recover");
+        assert_eq!(result.attempts, 3);
+        assert!(matches!(result.status, FileStatus::Updated(_)));
+    }
+
+    /// A successful fetch (no retries, no checksum mismatch) must record
+    /// `fetched_at` and `sha256` in the sidecar. `FlakySource` returns an
+    /// empty `FileMeta`, so both fields here come from `fetch_and_validate`
+    /// itself, whichever `SyncSource` produced the text.
+    #[tokio::test]
+    async fn fetch_and_validate_records_fetched_at_and_sha256_on_success() {
+        let source = FlakySource { attempts: Default::default(), fail_times: 0 };
+        let dir = scratch_dir("records-meta");
+        let result = fetch_and_validate(&source, &dir, "test.toml", RetryPolicy::default(), None, |s| Ok(s.len()))
+            .await
+            .expect("should succeed");
+        assert!(matches!(result.status, FileStatus::Updated(_)));
+        let meta = read_meta(&dir, "test.toml");
+        assert!(meta.fetched_at.is_some(), "fetched_at should be recorded after a successful fetch");
+        assert_eq!(meta.sha256.as_deref(), Some(sha256_hex(b"ok").as_str()));
+    }
+
+    #[test]
+    fn file_age_prefers_recorded_fetched_at_over_mtime() {
+        let dir = scratch_dir("fileage");
+        std::fs::write(dir.join("thing.toml"), b"data").unwrap();
+        let meta = FileMeta {
+            etag: None,
+            last_modified: None,
+            fetched_at: Some(unix_now().saturating_sub(3600)),
+            sha256: None,
+        };
+        write_meta(&dir, "thing.toml", &meta);
+        let age = file_age(&dir, "thing.toml").expect("file exists");
+        assert!(age.as_secs() >= 3600 && age.as_secs() < 3700, "age was {age:?}");
+    }
+
+    #[test]
+    fn file_age_falls_back_to_mtime_without_sidecar_meta() {
+        let dir = scratch_dir("fileage-fallback");
+        std::fs::write(dir.join("thing.toml"), b"data").unwrap();
+        let age = file_age(&dir, "thing.toml").expect("file exists");
+        assert!(age.as_secs() < 5, "age was {age:?}");
+    }
+
+    #[tokio::test]
+    async fn fetch_and_validate_reports_offline_after_exhausting_retries() {
+        let source = FlakySource { attempts: Default::default(), fail_times: 10 };
+        let retry = RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1) };
+        let result = fetch_and_validate(
+            &source,
+            &scratch_dir("offline"),
+            "test.toml",
+            retry,
+            None,
+            |s| Ok(s.len()),
+        )
+        .await
+        .expect("offline should not be a hard error");
+        assert!(matches!(result.status, FileStatus::Offline));
+        assert_eq!(result.attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn fetch_and_validate_rejects_content_not_matching_expected_checksum() {
+        let source = FlakySource { attempts: Default::default(), fail_times: 0 };
+        let retry = RetryPolicy::default();
+        let err = fetch_and_validate(
+            &source,
+            &scratch_dir("bad-checksum"),
+            "test.toml",
+            retry,
+            Some("not-the-real-hash"),
+            |s| Ok(s.len()),
+        )
+        .await
+        .expect_err("mismatched checksum should be rejected");
+        assert!(matches!(err, HfpError::Io(_)));
+    }
+
+    #[test]
+    fn verify_checksum_detects_tampering() {
+        let dir = scratch_dir("verify-checksum");
+        std::fs::write(dir.join("thing.toml"), b"original content").unwrap();
+        let meta = FileMeta {
+            sha256: Some(sha256_hex(b"original content")),
+            ..FileMeta::default()
+        };
+        write_meta(&dir, "thing.toml", &meta);
+        assert!(verify_checksum_in_dir(&dir, "thing.toml", "original content"));
+        assert!(!verify_checksum_in_dir(&dir, "thing.toml", "tampered content"));
+    }
+
+    #[test]
+    fn verify_checksum_accepts_anything_when_no_checksum_recorded() {
+        let dir = scratch_dir("verify-checksum-none");
+        assert!(verify_checksum_in_dir(&dir, "thing.toml", "anything"));
+    }
 }
@@ -0,0 +1,86 @@
+use hf_providers_core::filter::filter_providers;
+use hf_providers_core::model::Model;
+use hf_providers_core::provider::{ProviderInfo, ProviderStatus};
+
+fn make_provider(
+    name: &str,
+    status: ProviderStatus,
+    input_price: Option<f64>,
+    context_window: Option<u64>,
+    tools: Option<bool>,
+) -> ProviderInfo {
+    ProviderInfo {
+        name: name.to_string(),
+        status,
+        task: "conversational".to_string(),
+        provider_id: name.to_string(),
+        input_price_per_m: input_price,
+        output_price_per_m: input_price,
+        throughput_tps: None,
+        latency_s: None,
+        context_window,
+        supports_tools: tools,
+        supports_structured: None,
+    }
+}
+
+fn make_model(id: &str, providers: Vec<ProviderInfo>) -> Model {
+    Model {
+        id: id.to_string(),
+        pipeline_tag: Some("text-generation".to_string()),
+        likes: 0,
+        downloads: 0,
+        inference_status: None,
+        providers,
+        variants: Vec::new(),
+        tags: Vec::new(),
+        library_name: None,
+        license: None,
+        safetensors_params: None,
+    }
+}
+
+#[test]
+fn filters_across_multiple_models() {
+    let models = vec![
+        make_model(
+            "org/cheap-tooled",
+            vec![make_provider("a", ProviderStatus::Live, Some(0.5), Some(32000), Some(true))],
+        ),
+        make_model(
+            "org/expensive-tooled",
+            vec![make_provider("b", ProviderStatus::Live, Some(5.0), Some(32000), Some(true))],
+        ),
+        make_model(
+            "org/cheap-no-tools",
+            vec![make_provider("c", ProviderStatus::Live, Some(0.5), Some(32000), Some(false))],
+        ),
+    ];
+
+    let matches = filter_providers(
+        &models,
+        "tools = true AND input_price < 1.0 AND context_window >= 32000 AND status = live",
+    )
+    .expect("should parse and filter");
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].0.id, "org/cheap-tooled");
+    assert_eq!(matches[0].1.name, "a");
+}
+
+#[test]
+fn returns_empty_when_nothing_matches() {
+    let models = vec![make_model(
+        "org/model",
+        vec![make_provider("a", ProviderStatus::Staging, Some(0.1), None, None)],
+    )];
+    let matches = filter_providers(&models, "status = live").expect("should parse");
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn invalid_expression_is_an_error() {
+    let models: Vec<Model> = Vec::new();
+    assert!(filter_providers(&models, "tools = ").is_err());
+    assert!(filter_providers(&models, "(tools = true").is_err());
+}
@@ -1,5 +1,5 @@
 use hf_providers_core::model::Model;
-use hf_providers_core::provider::{ProviderInfo, ProviderStatus};
+use hf_providers_core::provider::{select_provider, ProviderInfo, ProviderStatus, SelectCriteria};
 
 fn make_provider(name: &str, output_price: Option<f64>, throughput: Option<f64>) -> ProviderInfo {
     ProviderInfo {
@@ -137,3 +137,102 @@ fn estimated_params_none_when_no_hint() {
     // "DeepSeek-R1" has no param hint
     assert!(model.estimated_params().is_none());
 }
+
+fn make_provider_full(
+    name: &str,
+    status: ProviderStatus,
+    input_price: Option<f64>,
+    output_price: Option<f64>,
+    throughput: Option<f64>,
+    latency: Option<f64>,
+    context_window: Option<u64>,
+    tools: Option<bool>,
+    structured: Option<bool>,
+) -> ProviderInfo {
+    ProviderInfo {
+        name: name.to_string(),
+        status,
+        task: "conversational".to_string(),
+        provider_id: name.to_string(),
+        input_price_per_m: input_price,
+        output_price_per_m: output_price,
+        throughput_tps: throughput,
+        latency_s: latency,
+        context_window,
+        supports_tools: tools,
+        supports_structured: structured,
+    }
+}
+
+#[test]
+fn select_provider_picks_cheapest_by_default() {
+    let model = make_model(vec![
+        make_provider_full("pricey", ProviderStatus::Live, Some(5.0), Some(10.0), Some(50.0), Some(0.5), Some(8192), None, None),
+        make_provider_full("cheap", ProviderStatus::Live, Some(0.5), Some(1.0), Some(50.0), Some(0.5), Some(8192), None, None),
+    ]);
+    let p = select_provider(&model, &SelectCriteria::default()).expect("should select");
+    assert_eq!(p.name, "cheap");
+}
+
+#[test]
+fn select_provider_skips_non_live() {
+    let model = make_model(vec![
+        make_provider_full("staging", ProviderStatus::Staging, Some(0.1), Some(0.1), Some(999.0), Some(0.01), Some(8192), None, None),
+        make_provider_full("live", ProviderStatus::Live, Some(1.0), Some(1.0), Some(10.0), Some(1.0), Some(8192), None, None),
+    ]);
+    let p = select_provider(&model, &SelectCriteria::default()).expect("should select");
+    assert_eq!(p.name, "live");
+}
+
+#[test]
+fn select_provider_requires_tools() {
+    let model = make_model(vec![
+        make_provider_full("no-tools", ProviderStatus::Live, Some(0.1), Some(0.1), Some(50.0), Some(0.1), Some(8192), Some(false), None),
+        make_provider_full("tools", ProviderStatus::Live, Some(5.0), Some(5.0), Some(50.0), Some(0.1), Some(8192), Some(true), None),
+    ]);
+    let criteria = SelectCriteria { require_tools: true, ..SelectCriteria::default() };
+    let p = select_provider(&model, &criteria).expect("should select");
+    assert_eq!(p.name, "tools");
+}
+
+#[test]
+fn select_provider_requires_min_context_window() {
+    let model = make_model(vec![
+        make_provider_full("short", ProviderStatus::Live, Some(0.1), Some(0.1), Some(50.0), Some(0.1), Some(4096), None, None),
+        make_provider_full("long", ProviderStatus::Live, Some(1.0), Some(1.0), Some(50.0), Some(0.1), Some(32768), None, None),
+    ]);
+    let criteria = SelectCriteria { min_context_window: Some(16384), ..SelectCriteria::default() };
+    let p = select_provider(&model, &criteria).expect("should select");
+    assert_eq!(p.name, "long");
+}
+
+#[test]
+fn select_provider_none_when_no_candidates_meet_requirements() {
+    let model = make_model(vec![make_provider_full(
+        "a", ProviderStatus::Live, Some(1.0), Some(1.0), Some(50.0), Some(0.1), Some(4096), Some(false), None,
+    )]);
+    let criteria = SelectCriteria { require_tools: true, ..SelectCriteria::default() };
+    assert!(select_provider(&model, &criteria).is_none());
+}
+
+#[test]
+fn select_provider_ranks_on_available_fields_only() {
+    // Neither candidate has pricing or latency, only throughput differs.
+    let model = make_model(vec![
+        make_provider_full("slow", ProviderStatus::Live, None, None, Some(10.0), None, Some(8192), None, None),
+        make_provider_full("fast", ProviderStatus::Live, None, None, Some(100.0), None, Some(8192), None, None),
+    ]);
+    let p = select_provider(&model, &SelectCriteria::default()).expect("should select");
+    assert_eq!(p.name, "fast");
+}
+
+#[test]
+fn select_provider_weights_throughput_over_cost() {
+    let model = make_model(vec![
+        make_provider_full("cheap-slow", ProviderStatus::Live, Some(0.1), Some(0.1), Some(10.0), Some(0.5), Some(8192), None, None),
+        make_provider_full("pricey-fast", ProviderStatus::Live, Some(5.0), Some(5.0), Some(500.0), Some(0.5), Some(8192), None, None),
+    ]);
+    let criteria = SelectCriteria { w_cost: 0.0, w_latency: 0.0, w_throughput: 1.0, ..SelectCriteria::default() };
+    let p = select_provider(&model, &criteria).expect("should select");
+    assert_eq!(p.name, "pricey-fast");
+}
@@ -1,4 +1,4 @@
-use hf_providers_core::api::parse_model;
+use hf_providers_core::api::{parse_model, parse_model_verbose, ParseWarning};
 use serde_json::json;
 
 #[test]
@@ -147,3 +147,72 @@ fn parse_provider_entry_missing_provider_field_skipped() {
     let model = parse_model(&data).expect("should parse");
     assert!(model.providers.is_empty(), "entry without 'provider' field should be skipped");
 }
+
+#[test]
+fn parse_model_verbose_missing_id_warns_and_returns_none() {
+    let data = json!({"likes": 100});
+    let (model, warnings) = parse_model_verbose(&data);
+    assert!(model.is_none());
+    assert_eq!(warnings, vec![ParseWarning::MissingId]);
+}
+
+#[test]
+fn parse_model_verbose_warns_on_missing_provider_field() {
+    let data = json!({
+        "id": "org/model",
+        "inferenceProviderMapping": [
+            {"status": "live", "task": "conversational"}
+        ]
+    });
+    let (model, warnings) = parse_model_verbose(&data);
+    assert!(model.expect("should parse").providers.is_empty());
+    assert_eq!(warnings, vec![ParseWarning::MissingProviderField { index: 0 }]);
+}
+
+#[test]
+fn parse_model_verbose_warns_on_unknown_status() {
+    let data = json!({
+        "id": "org/model",
+        "inferenceProviderMapping": [
+            {"provider": "novita", "status": "weird-new-state", "task": "conversational"}
+        ]
+    });
+    let (model, warnings) = parse_model_verbose(&data);
+    let model = model.expect("should parse");
+    assert_eq!(model.providers[0].status, hf_providers_core::ProviderStatus::Unknown);
+    assert!(warnings.contains(&ParseWarning::UnknownStatus {
+        provider: "novita".to_string(),
+        raw: Some("weird-new-state".to_string()),
+    }));
+}
+
+#[test]
+fn parse_model_verbose_warns_on_missing_pricing() {
+    let data = json!({
+        "id": "org/model",
+        "inferenceProviderMapping": [
+            {"provider": "sambanova", "status": "live", "task": "conversational"}
+        ]
+    });
+    let (_model, warnings) = parse_model_verbose(&data);
+    assert!(warnings.contains(&ParseWarning::MissingPricing { provider: "sambanova".to_string() }));
+}
+
+#[test]
+fn parse_model_verbose_no_warnings_for_clean_payload() {
+    let data = json!({
+        "id": "deepseek-ai/DeepSeek-R1",
+        "inferenceProviderMapping": [
+            {
+                "provider": "novita",
+                "providerId": "deepseek/deepseek-r1-turbo",
+                "status": "live",
+                "task": "conversational",
+                "providerDetails": {"pricing": {"input": 0.7, "output": 2.5}}
+            }
+        ]
+    });
+    let (model, warnings) = parse_model_verbose(&data);
+    assert!(model.is_some());
+    assert!(warnings.is_empty());
+}
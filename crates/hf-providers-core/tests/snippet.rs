@@ -1,6 +1,6 @@
 use hf_providers_core::model::Model;
 use hf_providers_core::provider::{ProviderInfo, ProviderStatus};
-use hf_providers_core::snippet::{generate, Lang};
+use hf_providers_core::snippet::{generate, generate_with_config, Lang, SnippetConfig};
 
 fn test_model() -> (Model, ProviderInfo) {
     let prov = ProviderInfo {
@@ -32,6 +32,14 @@ fn test_model() -> (Model, ProviderInfo) {
     (model, prov)
 }
 
+fn test_model_with_caps(supports_tools: Option<bool>, supports_structured: Option<bool>) -> (Model, ProviderInfo) {
+    let (mut model, mut prov) = test_model();
+    prov.supports_tools = supports_tools;
+    prov.supports_structured = supports_structured;
+    model.providers = vec![prov.clone()];
+    (model, prov)
+}
+
 #[test]
 fn python_snippet_contains_model_and_provider() {
     let (model, prov) = test_model();
@@ -59,3 +67,114 @@ fn javascript_snippet_contains_model_and_provider() {
     assert!(code.contains("together"), "must contain provider name");
     assert!(code.contains("@huggingface/inference"), "must import HF package");
 }
+
+#[test]
+fn typescript_snippet_contains_model_and_types() {
+    let (model, prov) = test_model();
+    let code = generate(&model, &prov, Lang::Typescript);
+    assert!(code.contains("deepseek-ai/DeepSeek-R1"), "must contain model id");
+    assert!(code.contains("together"), "must contain provider name");
+    assert!(code.contains("ChatCompletionInputMessage"), "must use a TS type");
+}
+
+#[test]
+fn go_snippet_contains_model_and_auth() {
+    let (model, prov) = test_model();
+    let code = generate(&model, &prov, Lang::Go);
+    assert!(code.contains("deepseek-ai/DeepSeek-R1"), "must contain model id");
+    assert!(code.contains("together"), "must contain provider name");
+    assert!(code.contains("HF_TOKEN"), "must reference token");
+    assert!(code.contains("package main"), "must be a Go program");
+}
+
+#[test]
+fn openai_python_snippet_uses_provider_id_and_router_base_url() {
+    let (model, prov) = test_model();
+    let code = generate(&model, &prov, Lang::OpenAIPython);
+    assert!(code.contains(&prov.provider_id), "must use provider_id as model string");
+    assert!(code.contains("router.huggingface.co/together/v1"), "must point at the router");
+    assert!(code.contains("from openai import OpenAI"), "must use the openai SDK");
+    assert!(code.contains("HF_TOKEN"), "must reference token");
+}
+
+#[test]
+fn openai_javascript_snippet_uses_provider_id_and_router_base_url() {
+    let (model, prov) = test_model();
+    let code = generate(&model, &prov, Lang::OpenAIJavascript);
+    assert!(code.contains(&prov.provider_id), "must use provider_id as model string");
+    assert!(code.contains("router.huggingface.co/together/v1"), "must point at the router");
+    assert!(code.contains("from \"openai\""), "must use the openai SDK");
+    assert!(code.contains("HF_TOKEN"), "must reference token");
+}
+
+#[test]
+fn python_snippet_demonstrates_tool_calling_when_supported() {
+    let (model, prov) = test_model_with_caps(Some(true), None);
+    let code = generate(&model, &prov, Lang::Python);
+    assert!(code.contains("tools = ["), "must define a tools list");
+    assert!(code.contains("tool_calls"), "must show a tool-call round trip");
+}
+
+#[test]
+fn python_snippet_notes_unsupported_tool_calling() {
+    let (model, prov) = test_model_with_caps(Some(false), None);
+    let code = generate(&model, &prov, Lang::Python);
+    assert!(code.contains("# Note:"), "must emit a commented note");
+    assert!(!code.contains("tool_calls"), "must not emit broken tool-call code");
+}
+
+#[test]
+fn python_snippet_omits_capability_sections_when_unknown() {
+    let (model, prov) = test_model_with_caps(None, None);
+    let code = generate(&model, &prov, Lang::Python);
+    assert!(!code.contains("tool_calls") && !code.contains("response_format"));
+}
+
+#[test]
+fn javascript_snippet_demonstrates_structured_output_when_supported() {
+    let (model, prov) = test_model_with_caps(None, Some(true));
+    let code = generate(&model, &prov, Lang::Javascript);
+    assert!(code.contains("responseFormat"), "must build a response_format");
+    assert!(code.contains("json_schema"), "must use a JSON schema");
+}
+
+#[test]
+fn curl_snippet_notes_unsupported_structured_output() {
+    let (model, prov) = test_model_with_caps(None, Some(false));
+    let code = generate(&model, &prov, Lang::Curl);
+    assert!(code.contains("# Note:"), "must emit a commented note");
+    assert!(!code.contains("response_format"), "must not emit broken response_format code");
+}
+
+#[test]
+fn curl_snippet_honors_custom_base_url_and_token_env() {
+    let (model, prov) = test_model();
+    let config = SnippetConfig {
+        base_url: "https://gateway.internal.example.com".to_string(),
+        token_env: "GATEWAY_TOKEN".to_string(),
+    };
+    let code = generate_with_config(&model, &prov, Lang::Curl, &config);
+    assert!(code.contains("https://gateway.internal.example.com/v1/chat/completions"));
+    assert!(code.contains("$GATEWAY_TOKEN"));
+    assert!(!code.contains("router.huggingface.co"));
+    assert!(!code.contains("HF_TOKEN"));
+}
+
+#[test]
+fn openai_python_snippet_honors_custom_base_url_and_token_env() {
+    let (model, prov) = test_model();
+    let config = SnippetConfig {
+        base_url: "https://gateway.internal.example.com".to_string(),
+        token_env: "GATEWAY_TOKEN".to_string(),
+    };
+    let code = generate_with_config(&model, &prov, Lang::OpenAIPython, &config);
+    assert!(code.contains("https://gateway.internal.example.com/together/v1"));
+    assert!(code.contains(r#"os.environ["GATEWAY_TOKEN"]"#));
+}
+
+#[test]
+fn default_config_matches_generate() {
+    let (model, prov) = test_model();
+    let code = generate_with_config(&model, &prov, Lang::Python, &SnippetConfig::default());
+    assert_eq!(code, generate(&model, &prov, Lang::Python));
+}
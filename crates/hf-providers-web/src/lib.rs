@@ -1,5 +1,6 @@
 use wasm_bindgen::prelude::*;
 
+use hf_providers_core::cloud::CloudOffering;
 use hf_providers_core::estimate;
 use hf_providers_core::hardware::{self, GpuSpec, Runtime};
 use hf_providers_core::model::Model;
@@ -39,18 +40,29 @@ fn parse_runtime(s: &str) -> Option<Runtime> {
 }
 
 fn parse_quant(s: &str) -> Option<estimate::Quant> {
-    match s.to_uppercase().as_str() {
+    match s.to_uppercase().replace('-', "_").as_str() {
+        "Q2K" | "Q2_K" => Some(estimate::Quant::Q2K),
+        "Q3K" | "Q3_K" => Some(estimate::Quant::Q3K),
         "Q4" => Some(estimate::Quant::Q4),
+        "Q5K" | "Q5_K" => Some(estimate::Quant::Q5K),
+        "Q6K" | "Q6_K" => Some(estimate::Quant::Q6K),
         "Q8" => Some(estimate::Quant::Q8),
+        "FP8" => Some(estimate::Quant::FP8),
         "FP16" => Some(estimate::Quant::FP16),
         _ => None,
     }
 }
 
-/// Estimate performance for a GPU + model + quant + runtime.
-/// params is f64 to avoid BigInt on the JS side.
+/// Estimate performance for a GPU + model + quant + runtime at a given
+/// context length. params/context_tokens are f64 to avoid BigInt on the JS side.
 #[wasm_bindgen]
-pub fn estimate_perf(gpu: JsValue, params: f64, quant: &str, runtime: &str) -> JsValue {
+pub fn estimate_perf(
+    gpu: JsValue,
+    params: f64,
+    quant: &str,
+    runtime: &str,
+    context_tokens: f64,
+) -> JsValue {
     let gpu: GpuSpec = match serde_wasm_bindgen::from_value(gpu) {
         Ok(g) => g,
         Err(_) => return JsValue::NULL,
@@ -63,14 +75,15 @@ pub fn estimate_perf(gpu: JsValue, params: f64, quant: &str, runtime: &str) -> J
         Some(r) => r,
         None => return JsValue::NULL,
     };
-    let est = estimate::estimate(&gpu, params as u64, q, rt);
+    let ctx = estimate::ContextSpec::approx_for_params(params as u64, context_tokens as u64);
+    let est = estimate::estimate(&gpu, params as u64, q, rt, &ctx);
     serde_wasm_bindgen::to_value(&est).unwrap_or(JsValue::NULL)
 }
 
-/// Pick the best quantization that fits this GPU for a model of `params` parameters.
-/// Returns [quant_label, Estimate] or null.
+/// Pick the best quantization that fits this GPU for a model of `params`
+/// parameters at a given context length. Returns [quant_label, Estimate] or null.
 #[wasm_bindgen]
-pub fn best_quant(gpu: JsValue, params: f64, runtime: &str) -> JsValue {
+pub fn best_quant(gpu: JsValue, params: f64, runtime: &str, context_tokens: f64) -> JsValue {
     let gpu: GpuSpec = match serde_wasm_bindgen::from_value(gpu) {
         Ok(g) => g,
         Err(_) => return JsValue::NULL,
@@ -79,7 +92,8 @@ pub fn best_quant(gpu: JsValue, params: f64, runtime: &str) -> JsValue {
         Some(r) => r,
         None => return JsValue::NULL,
     };
-    match estimate::best_quant(&gpu, params as u64, rt) {
+    let ctx = estimate::ContextSpec::approx_for_params(params as u64, context_tokens as u64);
+    match estimate::best_quant(&gpu, params as u64, rt, &ctx) {
         Some((q, est)) => {
             serde_wasm_bindgen::to_value(&(q.label(), est)).unwrap_or(JsValue::NULL)
         }
@@ -87,6 +101,41 @@ pub fn best_quant(gpu: JsValue, params: f64, runtime: &str) -> JsValue {
     }
 }
 
+/// Estimate performance across a pooled multi-GPU setup, e.g. a cloud
+/// offering's `gpu_count` + `interconnect` ("nvlink" or "pcie").
+#[wasm_bindgen]
+pub fn estimate_multi(
+    gpu: JsValue,
+    gpu_count: u32,
+    interconnect: Option<String>,
+    params: f64,
+    quant: &str,
+    runtime: &str,
+) -> JsValue {
+    let gpu: GpuSpec = match serde_wasm_bindgen::from_value(gpu) {
+        Ok(g) => g,
+        Err(_) => return JsValue::NULL,
+    };
+    let q = match parse_quant(quant) {
+        Some(q) => q,
+        None => return JsValue::NULL,
+    };
+    let rt = match parse_runtime(runtime) {
+        Some(r) => r,
+        None => return JsValue::NULL,
+    };
+    let est = estimate::estimate_multi(
+        &gpu,
+        gpu_count,
+        interconnect.as_deref(),
+        params as u64,
+        q,
+        rt,
+        &estimate::ContextSpec::default(),
+    );
+    serde_wasm_bindgen::to_value(&est).unwrap_or(JsValue::NULL)
+}
+
 // ---------------------------------------------------------------------------
 // Machine report (reference models on a given GPU)
 // ---------------------------------------------------------------------------
@@ -107,12 +156,13 @@ struct RuntimeResult {
     prefill: Option<f64>,
     fits: bool,
     weight_gb: f64,
+    kv_gb: f64,
 }
 
 /// Generate a machine report: for each reference model, estimate performance
-/// across all runtimes available on this GPU.
+/// across all runtimes available on this GPU at a given context length.
 #[wasm_bindgen]
-pub fn machine_report(gpu: JsValue) -> JsValue {
+pub fn machine_report(gpu: JsValue, context_tokens: f64) -> JsValue {
     let gpu: GpuSpec = match serde_wasm_bindgen::from_value(gpu) {
         Ok(g) => g,
         Err(_) => return JsValue::NULL,
@@ -121,9 +171,10 @@ pub fn machine_report(gpu: JsValue) -> JsValue {
     let mut results: Vec<MachineResult> = Vec::new();
 
     for rm in REFERENCE_MODELS {
+        let ctx = estimate::ContextSpec::approx_for_params(rm.params, context_tokens as u64);
         let mut rt_results = Vec::new();
         for &rt in &runtimes {
-            match estimate::best_quant(&gpu, rm.params, rt) {
+            match estimate::best_quant(&gpu, rm.params, rt, &ctx) {
                 Some((q, est)) => {
                     rt_results.push(RuntimeResult {
                         runtime: rt.to_string(),
@@ -132,10 +183,14 @@ pub fn machine_report(gpu: JsValue) -> JsValue {
                         prefill: est.prefill_tok_s,
                         fits: true,
                         weight_gb: est.weight_gb,
+                        kv_gb: est.kv_gb,
                     });
                 }
                 None => {
-                    let weight_gb = rm.params as f64 * 0.5 / 1e9; // Q4 weight
+                    // Even the smallest quant in the ladder doesn't fit; report
+                    // its footprint so the user sees the floor they're missing by.
+                    let weight_gb =
+                        rm.params as f64 * estimate::Quant::Q2K.bytes_per_param() / 1e9;
                     rt_results.push(RuntimeResult {
                         runtime: rt.to_string(),
                         quant: None,
@@ -143,6 +198,7 @@ pub fn machine_report(gpu: JsValue) -> JsValue {
                         prefill: None,
                         fits: false,
                         weight_gb,
+                        kv_gb: ctx.kv_gb(),
                     });
                 }
             }
@@ -158,6 +214,87 @@ pub fn machine_report(gpu: JsValue) -> JsValue {
     serde_wasm_bindgen::to_value(&results).unwrap_or(JsValue::NULL)
 }
 
+// ---------------------------------------------------------------------------
+// Cloud planner
+// ---------------------------------------------------------------------------
+
+#[derive(serde::Serialize)]
+struct CloudPlanResult {
+    name: String,
+    provider: String,
+    region: Vec<String>,
+    gpu: String,
+    gpu_count: u32,
+    quant: String,
+    decode_tok_s: f64,
+    cost_per_million: f64,
+    spot_cost_per_million: Option<f64>,
+    url: String,
+}
+
+/// For each `CloudOffering`, pick the highest-fidelity quant that still fits
+/// (scaled across `gpu_count` and its `interconnect`), then rank the
+/// offerings where the model fits by on-demand $/1M output tokens. Offerings
+/// whose `gpu` key isn't found in `gpus`, or where nothing fits, are dropped.
+#[wasm_bindgen]
+pub fn plan_cloud(offerings: JsValue, gpus: JsValue, params: f64, runtime: &str) -> JsValue {
+    let offerings: Vec<(String, CloudOffering)> = match serde_wasm_bindgen::from_value(offerings) {
+        Ok(o) => o,
+        Err(_) => return JsValue::NULL,
+    };
+    let gpus: Vec<(String, GpuSpec)> = match serde_wasm_bindgen::from_value(gpus) {
+        Ok(g) => g,
+        Err(_) => return JsValue::NULL,
+    };
+    let rt = match parse_runtime(runtime) {
+        Some(r) => r,
+        None => return JsValue::NULL,
+    };
+
+    // Mirrors `ContextSpec::default`'s context length assumption.
+    let ctx = estimate::ContextSpec::approx_for_params(params as u64, 4096);
+
+    let mut candidates: Vec<CloudPlanResult> = Vec::new();
+    for (_, offering) in &offerings {
+        let Some((_, gpu)) = hardware::find_gpu(&gpus, &offering.gpu) else {
+            continue;
+        };
+        let Some((q, est)) = estimate::best_quant_multi(
+            &gpu,
+            offering.gpu_count,
+            offering.interconnect.as_deref(),
+            params as u64,
+            rt,
+            &ctx,
+        ) else {
+            continue;
+        };
+        let Some(decode_tok_s) = est.decode_tok_s else {
+            continue;
+        };
+
+        candidates.push(CloudPlanResult {
+            name: offering.name.clone(),
+            provider: offering.provider.clone(),
+            region: offering.region.clone(),
+            gpu: offering.gpu.clone(),
+            gpu_count: offering.gpu_count,
+            quant: q.label().to_string(),
+            decode_tok_s,
+            cost_per_million: cost_per_million(offering.price_hr, decode_tok_s),
+            spot_cost_per_million: offering.spot_hr.map(|s| cost_per_million(s, decode_tok_s)),
+            url: offering.url.clone(),
+        });
+    }
+
+    candidates.sort_by(|a, b| {
+        a.cost_per_million
+            .partial_cmp(&b.cost_per_million)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    serde_wasm_bindgen::to_value(&candidates).unwrap_or(JsValue::NULL)
+}
+
 // ---------------------------------------------------------------------------
 // Reference models
 // ---------------------------------------------------------------------------